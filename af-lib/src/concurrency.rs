@@ -6,16 +6,22 @@
 
 //! Concurrency primitives and utilities.
 
+mod async_lazy;
 pub mod channel;
 pub mod fiber;
 pub mod future;
-pub(crate) mod runtime;
+mod latch;
+mod local_pool;
+pub mod runtime;
 pub(crate) mod scope;
 pub mod task;
 pub mod thread;
 
+pub use self::async_lazy::AsyncLazy;
 pub use self::channel::channel;
 pub use self::future::Future;
+pub use self::latch::Latch;
+pub use self::local_pool::{LocalPool, LocalTask};
 pub use once_cell::sync::{Lazy, OnceCell};
 
 /// Yields once to pending concurrent operations.
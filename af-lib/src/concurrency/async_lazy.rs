@@ -0,0 +1,159 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering::{AcqRel, Release};
+
+use event_listener::Event;
+
+use super::OnceCell;
+use crate::prelude::*;
+use crate::util::defer;
+
+/// A value that is lazily initialized by an async operation.
+///
+/// Unlike [`Lazy`][super::Lazy], initialization can wait on other async
+/// operations. The initializer runs at most once, and concurrent callers wait
+/// for it to finish instead of starting their own.
+pub struct AsyncLazy<T> {
+  event: Event,
+  initializing: AtomicBool,
+  value: OnceCell<T>,
+}
+
+impl<T> AsyncLazy<T> {
+  /// Creates a new, uninitialized value.
+  pub const fn new() -> Self {
+    Self { event: Event::new(), initializing: AtomicBool::new(false), value: OnceCell::new() }
+  }
+
+  /// Returns a reference to the value if it is initialized.
+  pub fn get(&self) -> Option<&T> {
+    self.value.get()
+  }
+
+  /// Returns a reference to the value, initializing it with the given async
+  /// operation if it is not already initialized.
+  ///
+  /// If another caller is already initializing the value, this function waits
+  /// for it to finish and `init` is dropped without being polled. If that
+  /// caller is canceled or panics, one of the waiting callers runs its own
+  /// `init` instead.
+  pub async fn get_or_init(&self, init: impl Future<Output = T>) -> &T {
+    let mut init = Some(init);
+
+    loop {
+      if let Some(value) = self.value.get() {
+        return value;
+      }
+
+      let listener = self.event.listen();
+
+      if let Some(value) = self.value.get() {
+        return value;
+      }
+
+      if !self.initializing.swap(true, AcqRel) {
+        // Always release the lock and wake the waiters, even if the operation
+        // is canceled or panics.
+
+        let _guard = defer(|| {
+          self.initializing.store(false, Release);
+          self.event.notify(usize::MAX);
+        });
+
+        // Another caller may have finished initializing between the checks
+        // above and acquiring the lock.
+
+        if let Some(value) = self.value.get() {
+          return value;
+        }
+
+        let value = init.take().expect("initializer already used").await;
+
+        return match self.value.try_insert(value) {
+          Ok(value) => value,
+          Err((value, _)) => value,
+        };
+      }
+
+      listener.await;
+    }
+  }
+}
+
+impl<T> Default for AsyncLazy<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::AtomicUsize;
+  use std::sync::atomic::Ordering::Relaxed;
+
+  use super::*;
+  use crate::concurrency::{cooperative_yield, fiber, join, task};
+  use crate::time::Duration;
+
+  #[async_test]
+  async fn should_initialize_once() {
+    static VALUE: AsyncLazy<usize> = AsyncLazy::new();
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    for _ in 0..4 {
+      fiber::start(async {
+        let value = VALUE
+          .get_or_init(async {
+            CALLS.fetch_add(1, Relaxed);
+            Duration::milliseconds(10).elapsed().await;
+            42
+          })
+          .await;
+
+        assert_eq!(*value, 42);
+      });
+    }
+
+    join().await;
+
+    assert_eq!(VALUE.get(), Some(&42));
+    assert_eq!(CALLS.load(Relaxed), 1);
+  }
+
+  #[async_test]
+  async fn should_initialize_once_across_threads() {
+    for _ in 0..100 {
+      let value = Arc::new(AsyncLazy::new());
+      let calls = Arc::new(AtomicUsize::new(0));
+
+      for _ in 0..8 {
+        let value = value.clone();
+        let calls = calls.clone();
+
+        task::start(async move {
+          let value = value
+            .get_or_init(async {
+              calls.fetch_add(1, Relaxed);
+              cooperative_yield().await;
+              42
+            })
+            .await;
+
+          assert_eq!(*value, 42);
+        });
+      }
+
+      join().await;
+
+      assert_eq!(value.get(), Some(&42));
+      assert_eq!(calls.load(Relaxed), 1);
+    }
+  }
+}
@@ -6,7 +6,14 @@
 
 //! A multi-producer, multi-consumer channel.
 
+use std::task::{Context, Poll};
+
+use event_listener::Event;
+use futures_lite::{ready, Stream};
+
+use super::future;
 use crate::prelude::*;
+use crate::time::{self, Duration};
 
 /// Creates a bounded channel and returns its [`BoundedSender`] and
 /// [`Receiver`] halves.
@@ -15,9 +22,39 @@ use crate::prelude::*;
 /// space to send messages. A channel with zero capacity is a “rendesvouz
 /// channel,” where every `send` must be paired with a concurrent `recv`.
 pub fn bounded<T>(capacity: usize) -> (BoundedSender<T>, Receiver<T>) {
+  bounded_with(capacity, OverflowPolicy::Block)
+}
+
+/// Creates a bounded channel with the given [`OverflowPolicy`] and returns its
+/// [`BoundedSender`] and [`Receiver`] halves.
+///
+/// With [`OverflowPolicy::Block`], this is equivalent to [`bounded()`]. With
+/// either of the other policies, sending never waits and always succeeds while
+/// the channel is open, discarding a message instead if the channel is full.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero and the policy is not `Block`.
+pub fn bounded_with<T>(capacity: usize, policy: OverflowPolicy) -> (BoundedSender<T>, Receiver<T>) {
+  assert!(
+    capacity > 0 || policy == OverflowPolicy::Block,
+    "a channel with zero capacity cannot drop messages"
+  );
+
   let (tx, rx) = flume::bounded(capacity);
+  let rx = Receiver::new(rx);
+
+  let evictor = match policy {
+    OverflowPolicy::DropOldest => {
+      Some(Evictor { rx: rx.rx.clone(), receivers: Arc::downgrade(&rx.alive) })
+    }
+
+    _ => None,
+  };
 
-  (BoundedSender(tx), Receiver(rx))
+  let capacity = rx.capacity.0.clone();
+
+  (BoundedSender { tx, policy, evictor, capacity }, rx)
 }
 
 /// Creates an unbounded channel and returns its [`Sender`] and [`Receiver`]
@@ -28,37 +65,67 @@ pub fn bounded<T>(capacity: usize) -> (BoundedSender<T>, Receiver<T>) {
 pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
   let (tx, rx) = flume::unbounded();
 
-  (Sender(tx), Receiver(rx))
+  (Sender(Arc::new(tx)), Receiver::new(rx))
 }
 
 /// A cloneable sender for a bounded channel.
-pub struct BoundedSender<T>(flume::Sender<T>);
+pub struct BoundedSender<T> {
+  tx: flume::Sender<T>,
+  policy: OverflowPolicy,
+  evictor: Option<Evictor<T>>,
+  capacity: Arc<Event>,
+}
+
+/// An event notified by a [`Receiver`] when it starts waiting for a message,
+/// receives a message, or is dropped, any of which may let a waiting sender
+/// send.
+struct CapacityEvent(Arc<Event>);
+
+/// A receiver used by [`OverflowPolicy::DropOldest`] senders to evict messages.
+///
+/// Because the evicting receiver keeps the channel open, closing is detected
+/// with a weak reference to the liveness token of the real receivers instead.
+struct Evictor<T> {
+  rx: flume::Receiver<T>,
+  receivers: ArcWeak<()>,
+}
 
 impl<T> BoundedSender<T> {
   /// Returns `true` if the channel is closed.
   ///
   /// The channel is closed if all [`Receiver`] clones are dropped.
   pub fn is_closed(&self) -> bool {
-    self.0.is_disconnected()
+    match &self.evictor {
+      Some(evictor) => evictor.receivers.strong_count() == 0,
+      None => self.tx.is_disconnected(),
+    }
   }
 
   /// Returns `true` if the channel has no messages.
   pub fn is_empty(&self) -> bool {
-    self.0.is_empty()
+    self.tx.is_empty()
   }
 
   /// Returns `true` if the channel has no remaining capacity for messages.
   pub fn is_full(&self) -> bool {
-    self.0.is_full()
+    self.tx.is_full()
   }
 
   /// Returns the number of messages in the channel.
   pub fn len(&self) -> usize {
-    self.0.len()
+    self.tx.len()
+  }
+
+  /// Returns the overflow policy of the channel.
+  pub fn overflow_policy(&self) -> OverflowPolicy {
+    self.policy
   }
 
   /// Waits for available capacity in the channel, then sends a message.
   ///
+  /// If the channel has a drop policy, this function never waits and is
+  /// equivalent to [`send_now()`][Self::send_now].
+  ///
   /// This function returns `true` if the message was sent or `false` if the
   /// channel is closed.
   pub async fn send(&self, message: T) -> bool {
@@ -67,44 +134,159 @@ impl<T> BoundedSender<T> {
 
   /// Attempts to send a message on the channel.
   ///
+  /// If the channel has a drop policy, this function never waits and is
+  /// equivalent to [`try_send_now()`][Self::try_send_now].
+  ///
   /// If the channel is closed, this function returns an error containing the
   /// failed message.
   pub async fn try_send(&self, message: T) -> Result<(), MessageError<T, Closed>> {
-    self.0.send_async(message).await.map_err(|err| MessageError { message: err.0, error: Closed })
+    if self.policy != OverflowPolicy::Block {
+      return self
+        .try_send_now(message)
+        .map_err(|err| MessageError { message: err.message, error: Closed });
+    }
+
+    self.tx.send_async(message).await.map_err(|err| MessageError { message: err.0, error: Closed })
+  }
+
+  /// Waits up to `timeout` for available capacity in the channel, then sends a
+  /// message.
+  ///
+  /// If the channel has a drop policy, this function never waits and is
+  /// equivalent to [`try_send_now()`][Self::try_send_now].
+  ///
+  /// If the timeout elapses or the channel is closed, this function returns an
+  /// error containing the failed message. Dropping the returned future cancels
+  /// the send.
+  pub async fn send_timeout(
+    &self,
+    message: T,
+    timeout: Duration,
+  ) -> Result<(), MessageError<T, SendTimeoutError>> {
+    // Keep the message outside of the timed operation so that it can be
+    // returned if the timeout elapses.
+
+    let mut message = Some(message);
+
+    let result = time::timeout(timeout, async {
+      loop {
+        let listener = self.capacity.listen();
+
+        match self.try_send_now(message.take().expect("message already sent")) {
+          Ok(()) => return Ok(()),
+
+          Err(MessageError { message, error: SendNowError::Closed }) => {
+            return Err(MessageError { message, error: SendTimeoutError::Closed });
+          }
+
+          Err(MessageError { message: unsent, error: SendNowError::Full }) => {
+            message = Some(unsent);
+          }
+        }
+
+        listener.await;
+      }
+    })
+    .await;
+
+    match result {
+      Ok(result) => result,
+
+      Err(time::timeout::Error) => Err(MessageError {
+        message: message.expect("message already sent"),
+        error: SendTimeoutError::Timeout,
+      }),
+    }
   }
 
   /// Sends a message on the channel immediately.
   ///
   /// This function returns `true` if the message was sent or `false` if the
-  /// channel is full or closed.
+  /// channel is full or closed. If the channel has a drop policy, it is never
+  /// considered full.
   pub fn send_now(&self, message: T) -> bool {
-    self.0.try_send(message).is_ok()
+    self.try_send_now(message).is_ok()
   }
 
   /// Attempts to send a message on the channel immediately.
   ///
-  /// If the channel is full or closed, this function returns an error
-  /// containing the failed message.
-  pub fn try_send_now(&self, message: T) -> Result<(), MessageError<T, SendNowError>> {
-    self.0.try_send(message).map_err(|err| match err {
-      flume::TrySendError::Disconnected(message) => {
-        MessageError { message, error: SendNowError::Closed }
-      }
+  /// If the channel is full, the [`OverflowPolicy`] of the channel determines
+  /// what happens:
+  ///
+  /// - `Block` returns an error containing the failed message.
+  /// - `DropOldest` discards the oldest message in the channel to make room.
+  /// - `DropNewest` discards the message and returns `Ok`.
+  ///
+  /// If the channel is closed, this function returns an error containing the
+  /// failed message.
+  pub fn try_send_now(&self, mut message: T) -> Result<(), MessageError<T, SendNowError>> {
+    if self.evictor.is_some() && self.is_closed() {
+      return Err(MessageError { message, error: SendNowError::Closed });
+    }
 
-      flume::TrySendError::Full(message) => MessageError { message, error: SendNowError::Full },
-    })
+    loop {
+      message = match self.tx.try_send(message) {
+        Ok(()) => return Ok(()),
+
+        Err(flume::TrySendError::Disconnected(message)) => {
+          return Err(MessageError { message, error: SendNowError::Closed });
+        }
+
+        Err(flume::TrySendError::Full(message)) => match (self.policy, &self.evictor) {
+          (OverflowPolicy::DropNewest, _) => return Ok(()),
+
+          (OverflowPolicy::DropOldest, Some(evictor)) => {
+            let _ = evictor.rx.try_recv();
+
+            message
+          }
+
+          _ => return Err(MessageError { message, error: SendNowError::Full }),
+        },
+      };
+    }
   }
 }
 
 impl<T> Clone for BoundedSender<T> {
   fn clone(&self) -> Self {
-    Self(self.0.clone())
+    Self {
+      tx: self.tx.clone(),
+      policy: self.policy,
+      evictor: self
+        .evictor
+        .as_ref()
+        .map(|evictor| Evictor { rx: evictor.rx.clone(), receivers: evictor.receivers.clone() }),
+      capacity: self.capacity.clone(),
+    }
   }
 }
 
 impl<T> From<Sender<T>> for BoundedSender<T> {
   fn from(sender: Sender<T>) -> Self {
-    BoundedSender(sender.0)
+    BoundedSender {
+      tx: (*sender.0).clone(),
+      policy: OverflowPolicy::Block,
+      evictor: None,
+      capacity: default(),
+    }
+  }
+}
+
+impl CapacityEvent {
+  /// Notifies senders waiting for capacity.
+  ///
+  /// This is called each time a receive operation is polled, because a
+  /// completed receive frees capacity and a pending one can take a message
+  /// directly from a sender, even on a channel with zero capacity.
+  fn notify_waiting_senders(&self) {
+    self.0.notify(usize::MAX);
+  }
+}
+
+impl Drop for CapacityEvent {
+  fn drop(&mut self) {
+    self.notify_waiting_senders();
   }
 }
 
@@ -141,25 +323,51 @@ where
 
 impl<M, E> Error for MessageError<M, E> where E: Debug + Display {}
 
+/// What a [`BoundedSender`] does with a message when its channel is full.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum OverflowPolicy {
+  /// Wait for available capacity, or fail if the message must be sent
+  /// immediately.
+  Block,
+  /// Discard the oldest message in the channel to make room.
+  DropOldest,
+  /// Discard the message being sent.
+  DropNewest,
+}
+
 /// A cloneable receiver for a channel.
-pub struct Receiver<T>(flume::Receiver<T>);
+pub struct Receiver<T> {
+  rx: flume::Receiver<T>,
+  alive: Arc<()>,
+  /// The pending receive operation of the [`Stream`] implementation.
+  next: Option<RecvFuture<T>>,
+  // Declared after `rx` so that waiting senders are notified after the channel
+  // is closed.
+  capacity: CapacityEvent,
+}
 
 impl<T> Receiver<T> {
+  /// Creates a new receiver from a flume receiver.
+  fn new(rx: flume::Receiver<T>) -> Self {
+    Self { rx, alive: default(), next: None, capacity: CapacityEvent(default()) }
+  }
+
   /// Returns `true` if the channel is closed.
   ///
-  /// The channel is closed if all [`Sender`] clones are dropped.
+  /// The channel is closed if all [`Sender`] clones are dropped, even if
+  /// [`WeakSender`] clones remain.
   pub fn is_closed(&self) -> bool {
-    self.0.is_disconnected()
+    self.rx.is_disconnected()
   }
 
   /// Returns `true` if no messages are queued on the channel.
   pub fn is_empty(&self) -> bool {
-    self.0.is_empty()
+    self.rx.is_empty()
   }
 
   /// Returns the number of messages queued in the channel.
   pub fn len(&self) -> usize {
-    self.0.len()
+    self.rx.len()
   }
 
   /// Waits for an available message in the channel and receives it.
@@ -178,7 +386,16 @@ impl<T> Receiver<T> {
   ///
   /// If the channel is closed, this function returns an error.
   pub async fn try_recv(&self) -> Result<T, Closed> {
-    self.0.recv_async().await.map_err(|_| Closed)
+    let mut recv = self.rx.recv_async();
+
+    future::from_fn(|cx| {
+      let result = Pin::new(&mut recv).poll(cx);
+
+      self.capacity.notify_waiting_senders();
+
+      result.map(|result| result.map_err(|_| Closed))
+    })
+    .await
   }
 
   /// Attempts to immediately receive a message from the channel if one is
@@ -186,8 +403,13 @@ impl<T> Receiver<T> {
   ///
   /// If the channel is closed, this function returns an error.
   pub fn try_recv_now(&self) -> Result<Option<T>, Closed> {
-    match self.0.try_recv() {
-      Ok(msg) => Ok(Some(msg)),
+    match self.rx.try_recv() {
+      Ok(msg) => {
+        self.capacity.notify_waiting_senders();
+
+        Ok(Some(msg))
+      }
+
       Err(flume::TryRecvError::Empty) => Ok(None),
       Err(flume::TryRecvError::Disconnected) => Err(Closed),
     }
@@ -196,14 +418,57 @@ impl<T> Receiver<T> {
 
 impl<T> Clone for Receiver<T> {
   fn clone(&self) -> Self {
-    Self(self.0.clone())
+    Self {
+      rx: self.rx.clone(),
+      alive: self.alive.clone(),
+      next: None,
+      capacity: CapacityEvent(self.capacity.0.clone()),
+    }
   }
 }
 
+// Implement `Stream` to receive messages until the channel is closed.
+
+impl<T> Stream for Receiver<T>
+where
+  T: Send + 'static,
+{
+  type Item = T;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+    let this = &mut *self;
+    let rx = &this.rx;
+    let next = this.next.get_or_insert_with(|| Box::pin(rx.clone().into_recv_async()));
+    let result = next.as_mut().poll(cx);
+
+    this.capacity.notify_waiting_senders();
+
+    let result = ready!(result);
+
+    this.next = None;
+
+    Poll::Ready(result.ok())
+  }
+}
+
+/// A boxed receive operation of a [`Receiver`].
+type RecvFuture<T> = Pin<Box<dyn Future<Output = Result<T, flume::RecvError>> + Send + Sync>>;
+
 /// A cloneable sender for an unbounded channel.
-pub struct Sender<T>(flume::Sender<T>);
+///
+/// Clones share a single underlying sender so that they can be downgraded to a
+/// [`WeakSender`].
+pub struct Sender<T>(Arc<flume::Sender<T>>);
 
 impl<T> Sender<T> {
+  /// Creates a [`WeakSender`] for the channel.
+  ///
+  /// Weak senders do not keep the channel open, so they can be used to break
+  /// reference cycles between channels.
+  pub fn downgrade(&self) -> WeakSender<T> {
+    WeakSender(Arc::downgrade(&self.0))
+  }
+
   /// Returns `true` if the channel is closed.
   ///
   /// The channel is closed if all [`Receiver`] clones are dropped.
@@ -260,3 +525,191 @@ pub enum SendNowError {
   #[error("channel is full")]
   Full,
 }
+
+/// An error returned from a send attempt with a timeout.
+#[derive(Debug, Error)]
+pub enum SendTimeoutError {
+  /// Channel is closed.
+  #[error("channel is closed")]
+  Closed,
+  /// Timed out waiting for capacity.
+  #[error("timed out waiting for capacity")]
+  Timeout,
+}
+
+/// A weak reference to a [`Sender`] that does not keep the channel open.
+pub struct WeakSender<T>(ArcWeak<flume::Sender<T>>);
+
+impl<T> WeakSender<T> {
+  /// Attempts to upgrade to a [`Sender`].
+  ///
+  /// This function returns `None` if all [`Sender`] clones have been dropped.
+  pub fn upgrade(&self) -> Option<Sender<T>> {
+    self.0.upgrade().map(Sender)
+  }
+}
+
+impl<T> Clone for WeakSender<T> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::concurrency::{fiber, join};
+
+  #[async_test]
+  async fn receiver_should_be_a_stream() {
+    use futures_lite::StreamExt;
+
+    let (tx, rx) = channel();
+
+    tx.send(1);
+    tx.send(2);
+    tx.send(3);
+
+    drop(tx);
+
+    assert_eq!(rx.collect::<Vec<_>>().await, [1, 2, 3]);
+  }
+
+  #[test]
+  fn weak_sender_should_not_keep_channel_open() {
+    let (tx, rx) = channel::<()>();
+    let weak = tx.downgrade();
+
+    assert!(weak.upgrade().is_some());
+    assert!(!rx.is_closed());
+
+    drop(tx);
+
+    assert!(weak.upgrade().is_none());
+    assert!(rx.is_closed());
+  }
+
+  #[async_test]
+  async fn send_timeout_should_return_message() {
+    let (tx, rx) = bounded(1);
+
+    assert!(tx.send_now(1));
+
+    let result = tx.send_timeout(2, Duration::milliseconds(10)).await;
+
+    assert!(matches!(result, Err(MessageError { message: 2, error: SendTimeoutError::Timeout })));
+
+    // Receiving a message frees capacity for a waiting sender.
+
+    fiber::start({
+      let rx = rx.clone();
+
+      async move {
+        Duration::milliseconds(10).elapsed().await;
+        rx.recv().await;
+      }
+    });
+
+    assert!(tx.send_timeout(3, Duration::seconds(5)).await.is_ok());
+    assert_eq!(rx.recv_now(), Some(3));
+
+    // Dropping all receivers closes the channel.
+
+    assert!(tx.send_now(4));
+
+    fiber::start(async move {
+      Duration::milliseconds(10).elapsed().await;
+      drop(rx);
+    });
+
+    let result = tx.send_timeout(5, Duration::seconds(5)).await;
+
+    assert!(matches!(result, Err(MessageError { message: 5, error: SendTimeoutError::Closed })));
+  }
+
+  #[async_test]
+  async fn send_timeout_should_cancel_when_dropped() {
+    use crate::concurrency::future::FutureTimeExt;
+
+    let (tx, rx) = bounded(1);
+
+    assert!(tx.send_now(1));
+
+    let result = tx
+      .send_timeout(2, Duration::forever())
+      .or_else_after(Duration::milliseconds(10), || Ok(()))
+      .await;
+
+    assert!(result.is_ok());
+    assert_eq!(rx.recv_now(), Some(1));
+    assert_eq!(rx.recv_now(), None);
+  }
+
+  #[async_test]
+  async fn send_timeout_should_rendezvous_with_late_receiver() {
+    let (tx, rx) = bounded(0);
+
+    fiber::start(async move {
+      Duration::milliseconds(10).elapsed().await;
+
+      assert_eq!(rx.recv().await, Some(1));
+    });
+
+    assert!(tx.send_timeout(1, Duration::seconds(5)).await.is_ok());
+
+    join().await;
+  }
+
+  #[test]
+  fn block_should_reject_when_full() {
+    let (tx, rx) = bounded_with(2, OverflowPolicy::Block);
+
+    assert!(tx.send_now(1));
+    assert!(tx.send_now(2));
+    assert!(matches!(tx.try_send_now(3), Err(MessageError { message: 3, .. })));
+    assert_eq!(rx.recv_now(), Some(1));
+    assert_eq!(rx.recv_now(), Some(2));
+    assert_eq!(rx.recv_now(), None);
+  }
+
+  #[test]
+  fn drop_oldest_should_evict_head() {
+    let (tx, rx) = bounded_with(2, OverflowPolicy::DropOldest);
+
+    assert!(tx.send_now(1));
+    assert!(tx.send_now(2));
+    assert!(tx.send_now(3));
+    assert_eq!(rx.recv_now(), Some(2));
+    assert_eq!(rx.recv_now(), Some(3));
+    assert_eq!(rx.recv_now(), None);
+  }
+
+  #[test]
+  fn drop_oldest_should_close() {
+    let (tx, rx) = bounded_with(1, OverflowPolicy::DropOldest);
+    let rx2 = rx.clone();
+
+    drop(rx);
+
+    assert!(!tx.is_closed());
+
+    drop(rx2);
+
+    assert!(tx.is_closed());
+    assert!(!tx.send_now(1));
+  }
+
+  #[test]
+  fn drop_newest_should_discard_incoming() {
+    let (tx, rx) = bounded_with(2, OverflowPolicy::DropNewest);
+
+    assert!(tx.send_now(1));
+    assert!(tx.send_now(2));
+    assert!(tx.send_now(3));
+    assert_eq!(rx.recv_now(), Some(1));
+    assert_eq!(rx.recv_now(), Some(2));
+    assert_eq!(rx.recv_now(), None);
+  }
+}
@@ -11,7 +11,11 @@ pub use std::task::{Context, Poll};
 
 pub use futures_lite::ready;
 
+mod noop_waker;
+
+use super::runtime;
 use crate::prelude::*;
+use crate::time::Duration;
 use crate::util::{panic, Panic};
 
 /// Waits for an async operation to complete, capturing panic information if one
@@ -30,11 +34,89 @@ where
   })
 }
 
+/// Creates an async operation that is completed by polling a closure.
+///
+/// The closure is called each time the operation is polled, and the operation
+/// completes when it returns [`Poll::Ready`]. If it returns [`Poll::Pending`],
+/// it is responsible for arranging for the waker in the given [`Context`] to be
+/// woken.
+pub fn from_fn<T>(poll: impl FnMut(&mut Context) -> Poll<T>) -> impl Future<Output = T> {
+  futures_lite::future::poll_fn(poll)
+}
+
+/// Waits for all async operations in a collection to complete and returns
+/// their outputs in the same order.
+///
+/// The operations are polled concurrently on the current task, so they do not
+/// need to be [`Send`].
+pub fn join_all<F>(ops: impl IntoIterator<Item = F>) -> impl Future<Output = Vec<F::Output>>
+where
+  F: Future,
+{
+  let mut ops: Vec<_> = ops.into_iter().map(|op| Some(Box::pin(op))).collect();
+  let mut outputs: Vec<_> = ops.iter().map(|_| None).collect();
+
+  from_fn(move |cx| {
+    let mut is_pending = false;
+
+    for (op, output) in ops.iter_mut().zip(&mut outputs) {
+      if let Some(future) = op {
+        match future.as_mut().poll(cx) {
+          Poll::Ready(value) => {
+            *output = Some(value);
+            *op = None;
+          }
+
+          Poll::Pending => is_pending = true,
+        }
+      }
+    }
+
+    match is_pending {
+      true => Poll::Pending,
+      false => Poll::Ready(
+        outputs.drain(..).map(|output| output.expect("polled after completion")).collect(),
+      ),
+    }
+  })
+}
+
 /// Waits forever.
 pub async fn never() {
   futures_lite::future::pending().await
 }
 
+/// Creates an async operation that never completes.
+///
+/// Unlike [`never()`], the operation can be used where an output of any type is
+/// expected.
+pub fn pending<T>() -> impl Future<Output = T> {
+  futures_lite::future::pending()
+}
+
+/// Polls an async operation once without waiting, returning its output if it
+/// is complete.
+///
+/// The operation is polled with a waker that does nothing, so it is not woken
+/// if it is pending.
+pub fn poll<F>(op: &mut F) -> Option<F::Output>
+where
+  F: Future + Unpin,
+{
+  match Pin::new(op).poll(&mut noop_waker::context()) {
+    Poll::Ready(output) => Some(output),
+    Poll::Pending => None,
+  }
+}
+
+/// Creates an async operation that is completed by polling a closure.
+///
+/// This is the same as [`from_fn()`], under the name used by the standard
+/// library and `futures`.
+pub fn poll_fn<T>(poll: impl FnMut(&mut Context) -> Poll<T>) -> impl Future<Output = T> {
+  from_fn(poll)
+}
+
 /// Waits for one of two async operations to complete and returns its output.
 ///
 /// The remaining operation is canceled. If both operations complete at the same
@@ -45,6 +127,115 @@ pub fn race<T>(a: impl Future<Output = T>, b: impl Future<Output = T>) -> impl F
   a.or(b)
 }
 
+/// Waits for one of two async operations to complete and returns its output
+/// along with the remaining operation.
+///
+/// Unlike [`race()`], the remaining operation is not canceled. It can be
+/// awaited later to get its own output, or dropped to cancel it. If both
+/// operations complete at the same time, the output of the first is returned.
+pub fn race_keep<A, B>(
+  a: A,
+  b: B,
+) -> impl Future<Output = (Either<A::Output, B::Output>, Remaining<A, B>)>
+where
+  A: Future,
+  B: Future,
+{
+  let mut ops = Some((Box::pin(a), Box::pin(b)));
+
+  from_fn(move |cx| {
+    let (a, b) = ops.as_mut().expect("polled after completion");
+
+    if let Poll::Ready(output) = a.as_mut().poll(cx) {
+      let (_, b) = ops.take().unwrap();
+
+      return Poll::Ready((Either::Left(output), Remaining::Right(b)));
+    }
+
+    if let Poll::Ready(output) = b.as_mut().poll(cx) {
+      let (a, _) = ops.take().unwrap();
+
+      return Poll::Ready((Either::Right(output), Remaining::Left(a)));
+    }
+
+    Poll::Pending
+  })
+}
+
+/// Creates an async operation that completes immediately with the given value.
+pub fn ready<T>(value: T) -> impl Future<Output = T> {
+  futures_lite::future::ready(value)
+}
+
+/// Runs a fallible async operation, retrying it with backoff until it
+/// succeeds.
+///
+/// After each failed attempt, this function waits for a delay that starts at
+/// [`RetryPolicy::initial_delay`] and is multiplied by
+/// [`RetryPolicy::multiplier`] after each retry. If the operation still fails
+/// after [`RetryPolicy::max_attempts`], or if `should_retry` returns `false`
+/// for an error, that error is returned.
+pub async fn retry<T, E, F>(
+  policy: RetryPolicy,
+  should_retry: impl Fn(&E) -> bool,
+  mut op: impl FnMut() -> F,
+) -> Result<T, E>
+where
+  F: Future<Output = Result<T, E>>,
+{
+  let mut attempts = 1;
+  let mut delay = policy.initial_delay;
+
+  loop {
+    match op().await {
+      Ok(output) => return Ok(output),
+      Err(err) if attempts >= policy.max_attempts || !should_retry(&err) => return Err(err),
+      Err(_) => {}
+    }
+
+    delay.elapsed().await;
+    delay *= policy.multiplier;
+    attempts += 1;
+  }
+}
+
+/// Waits for one of many async operations to complete and returns its output,
+/// its index, and the remaining operations.
+///
+/// The remaining operations are not canceled and keep their relative order, so
+/// they can be passed to this function again to wait for the next one. If
+/// multiple operations complete at the same time, the output of the first is
+/// returned. Operations that are not [`Unpin`] can be pinned with
+/// [`Box::pin()`].
+///
+/// # Panics
+///
+/// Panics if `ops` is empty.
+pub fn select_all<F>(ops: Vec<F>) -> impl Future<Output = (F::Output, usize, Vec<F>)>
+where
+  F: Future + Unpin,
+{
+  assert!(!ops.is_empty(), "select_all() requires at least one async operation");
+
+  let mut ops = Some(ops);
+
+  from_fn(move |cx| {
+    let pending = ops.as_mut().expect("polled after completion");
+
+    for (index, op) in pending.iter_mut().enumerate() {
+      if let Poll::Ready(output) = Pin::new(op).poll(cx) {
+        let mut remaining = ops.take().unwrap();
+
+        remaining.remove(index);
+
+        return Poll::Ready((output, index, remaining));
+      }
+    }
+
+    Poll::Pending
+  })
+}
+
 /// Waits for an async operation to complete by polling it with a custom
 /// closure.
 pub fn with_poll_fn<O, F>(
@@ -80,3 +271,285 @@ where
 
   WithPollFn { op, poll }
 }
+
+/// One of two possible values.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Either<A, B> {
+  /// The first possible value.
+  Left(A),
+  /// The second possible value.
+  Right(B),
+}
+
+/// An extension trait for async operations that adds time-based combinators.
+pub trait FutureTimeExt: Future + Sized {
+  /// Waits for the operation to complete with a timeout, returning a fallback
+  /// value from `fallback` if the timeout elapses first.
+  ///
+  /// Unlike [`timeout()`][crate::time::timeout()], this never fails, so it is
+  /// useful when a sensible default exists. The operation is dropped if the
+  /// timeout elapses.
+  fn or_else_after<G>(self, duration: Duration, fallback: G) -> OrElseAfter<Self, G>
+  where
+    G: FnOnce() -> Self::Output,
+  {
+    OrElseAfter { op: self, timeout: runtime::sleep(duration), fallback: Some(fallback) }
+  }
+}
+
+impl<F: Future> FutureTimeExt for F {}
+
+/// An async operation returned from [`FutureTimeExt::or_else_after()`].
+#[pin_project]
+pub struct OrElseAfter<F, G> {
+  #[pin]
+  op: F,
+  #[pin]
+  timeout: runtime::Sleep,
+  fallback: Option<G>,
+}
+
+impl<F, G> Future for OrElseAfter<F, G>
+where
+  F: Future,
+  G: FnOnce() -> F::Output,
+{
+  type Output = F::Output;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+    let this = self.project();
+
+    if let Poll::Ready(output) = this.op.poll(cx) {
+      return Poll::Ready(output);
+    }
+
+    ready!(this.timeout.poll(cx));
+
+    let fallback = this.fallback.take().expect("polled after completion");
+
+    Poll::Ready(fallback())
+  }
+}
+
+/// Options for [`retry()`].
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+  /// The maximum number of times to run the operation, including the first
+  /// attempt.
+  pub max_attempts: usize,
+  /// The delay before the first retry.
+  pub initial_delay: Duration,
+  /// The factor to multiply the delay by after each retry.
+  pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self { max_attempts: 3, initial_delay: Duration::milliseconds(100), multiplier: 2.0 }
+  }
+}
+
+/// The remaining operation returned from [`race_keep()`].
+///
+/// The operation continues when this future is awaited and is canceled when it
+/// is dropped.
+pub enum Remaining<A, B> {
+  /// The first operation, which remains because the second completed.
+  Left(Pin<Box<A>>),
+  /// The second operation, which remains because the first completed.
+  Right(Pin<Box<B>>),
+}
+
+impl<A, B> Future for Remaining<A, B>
+where
+  A: Future,
+  B: Future,
+{
+  type Output = Either<A::Output, B::Output>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+    match self.get_mut() {
+      Remaining::Left(op) => op.as_mut().poll(cx).map(Either::Left),
+      Remaining::Right(op) => op.as_mut().poll(cx).map(Either::Right),
+    }
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::time::Duration;
+
+  #[async_test]
+  async fn from_fn_should_poll_until_ready() {
+    let mut polls = 0;
+
+    let output = from_fn(|cx| {
+      polls += 1;
+
+      if polls < 3 {
+        cx.waker().wake_by_ref();
+        return Poll::Pending;
+      }
+
+      Poll::Ready(polls)
+    })
+    .await;
+
+    assert_eq!(output, 3);
+  }
+
+  #[async_test]
+  async fn ready_should_complete_immediately() {
+    assert_eq!(ready(5).await, 5);
+  }
+
+  #[async_test]
+  async fn join_all_should_preserve_order() {
+    let ops = [30, 10, 20].iter().map(|&ms| async move {
+      Duration::milliseconds(ms).elapsed().await;
+      ms
+    });
+
+    assert_eq!(join_all(ops).await, [30, 10, 20]);
+  }
+
+  #[async_test]
+  async fn poll_fn_should_poll_until_ready() {
+    let mut polls = 0;
+
+    let output = poll_fn(|cx| {
+      polls += 1;
+
+      match polls {
+        1 => {
+          cx.waker().wake_by_ref();
+          Poll::Pending
+        }
+
+        n => Poll::Ready(n),
+      }
+    })
+    .await;
+
+    assert_eq!(output, 2);
+  }
+
+  #[test]
+  fn poll_should_return_none_if_pending() {
+    assert_eq!(poll(&mut pending::<()>()), None);
+    assert_eq!(poll(&mut Box::pin(ready(5))), Some(5));
+  }
+
+  #[async_test]
+  async fn or_else_after_should_return_fallback() {
+    let slow = async {
+      Duration::milliseconds(50).elapsed().await;
+      "slow"
+    };
+
+    let fast = async {
+      Duration::milliseconds(1).elapsed().await;
+      "fast"
+    };
+
+    assert_eq!(slow.or_else_after(Duration::milliseconds(10), || "fallback").await, "fallback");
+    assert_eq!(fast.or_else_after(Duration::milliseconds(50), || "fallback").await, "fast");
+  }
+
+  #[async_test]
+  async fn race_keep_should_return_remaining_op() {
+    let fast = async { 1 };
+
+    let slow = async {
+      Duration::milliseconds(10).elapsed().await;
+      "slow"
+    };
+
+    let (winner, remaining) = race_keep(fast, slow).await;
+
+    assert_eq!(winner, Either::Left(1));
+    assert_eq!(remaining.await, Either::Right("slow"));
+  }
+
+  #[async_test]
+  async fn retry_should_succeed_after_failures() {
+    let policy = RetryPolicy { initial_delay: Duration::milliseconds(1), ..default() };
+    let attempts = Cell::new(0);
+
+    let result = retry(
+      policy,
+      |_| true,
+      || async {
+        attempts.set(attempts.get() + 1);
+
+        match attempts.get() {
+          3 => Ok("done"),
+          _ => Err("not yet"),
+        }
+      },
+    )
+    .await;
+
+    assert_eq!(result, Ok("done"));
+    assert_eq!(attempts.get(), 3);
+  }
+
+  #[async_test]
+  async fn retry_should_give_up() {
+    let policy =
+      RetryPolicy { max_attempts: 4, initial_delay: Duration::milliseconds(1), ..default() };
+    let attempts = Cell::new(0);
+
+    let result: Result<(), _> = retry(
+      policy,
+      |_| true,
+      || async {
+        attempts.set(attempts.get() + 1);
+        Err(attempts.get())
+      },
+    )
+    .await;
+
+    assert_eq!(result, Err(4));
+
+    let result: Result<(), _> = retry(
+      policy,
+      |err| *err < 6,
+      || async {
+        attempts.set(attempts.get() + 1);
+        Err(attempts.get())
+      },
+    )
+    .await;
+
+    assert_eq!(result, Err(6));
+  }
+
+  #[async_test]
+  async fn select_all_should_return_ops_in_completion_order() {
+    let ops: Vec<_> = [30, 10, 20]
+      .iter()
+      .map(|&ms| {
+        Box::pin(async move {
+          Duration::milliseconds(ms).elapsed().await;
+          ms
+        })
+      })
+      .collect();
+
+    let (output, index, ops) = select_all(ops).await;
+
+    assert_eq!((output, index, ops.len()), (10, 1, 2));
+
+    let (output, index, ops) = select_all(ops).await;
+
+    assert_eq!((output, index, ops.len()), (20, 1, 1));
+
+    let (output, index, ops) = select_all(ops).await;
+
+    assert_eq!((output, index, ops.len()), (30, 0, 0));
+  }
+}
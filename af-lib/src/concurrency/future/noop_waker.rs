@@ -18,7 +18,7 @@ pub fn context() -> Context<'static> {
 
 /// Returns a [`Waker`] singleton that does nothing.
 pub fn instance() -> &'static Waker {
-  &*INSTANCE
+  &INSTANCE
 }
 
 /// Creates the raw no-op waker.
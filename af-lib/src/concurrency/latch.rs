@@ -0,0 +1,128 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A value that is set once and broadcast to all waiting operations.
+
+use event_listener::Event;
+
+use super::OnceCell;
+use crate::prelude::*;
+
+/// A cloneable latch that is set once with a value that is then broadcast to
+/// all current and future waiters.
+pub struct Latch<T> {
+  inner: Arc<Inner<T>>,
+}
+
+/// The shared state of a latch.
+struct Inner<T> {
+  event: Event,
+  value: OnceCell<T>,
+}
+
+impl<T> Latch<T>
+where
+  T: Clone,
+{
+  /// Creates a new latch that has not been set.
+  pub fn new() -> Self {
+    Self { inner: Arc::new(Inner { event: Event::new(), value: OnceCell::new() }) }
+  }
+
+  /// Waits for the latch to be set and returns a clone of its value.
+  ///
+  /// If the latch is already set, this function returns immediately.
+  pub async fn get(&self) -> T {
+    loop {
+      if let Some(value) = self.try_get() {
+        return value;
+      }
+
+      let listener = self.inner.event.listen();
+
+      if let Some(value) = self.try_get() {
+        return value;
+      }
+
+      listener.await;
+    }
+  }
+
+  /// Returns `true` if the latch is set.
+  pub fn is_set(&self) -> bool {
+    self.inner.value.get().is_some()
+  }
+
+  /// Sets the value of the latch and wakes all waiting operations.
+  ///
+  /// This function returns `true` if the value was set or `false` if the latch
+  /// was already set, in which case the value is dropped.
+  pub fn set(&self, value: T) -> bool {
+    if self.inner.value.set(value).is_err() {
+      return false;
+    }
+
+    self.inner.event.notify(usize::MAX);
+
+    true
+  }
+
+  /// Returns a clone of the value of the latch if it is set.
+  pub fn try_get(&self) -> Option<T> {
+    self.inner.value.get().cloned()
+  }
+}
+
+impl<T> Clone for Latch<T> {
+  fn clone(&self) -> Self {
+    Self { inner: self.inner.clone() }
+  }
+}
+
+impl<T> Default for Latch<T>
+where
+  T: Clone,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::concurrency::{channel, fiber, join};
+  use crate::time::Duration;
+
+  #[async_test]
+  async fn should_broadcast_to_early_and_late_waiters() {
+    let latch = Latch::new();
+    let (tx, rx) = channel();
+
+    for _ in 0..2 {
+      let latch = latch.clone();
+      let tx = tx.clone();
+
+      fiber::start(async move {
+        tx.send(latch.get().await);
+      });
+    }
+
+    Duration::milliseconds(10).elapsed().await;
+
+    assert!(rx.is_empty());
+    assert!(latch.set("value"));
+    assert!(!latch.set("other"));
+
+    join().await;
+
+    assert_eq!(rx.recv().await, Some("value"));
+    assert_eq!(rx.recv().await, Some("value"));
+    assert_eq!(latch.get().await, "value");
+  }
+}
@@ -0,0 +1,108 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A dedicated thread for running async operations that are not [`Send`].
+
+use super::{channel, runtime};
+use crate::prelude::*;
+
+/// A cloneable handle to a dedicated thread that runs async operations that
+/// are not [`Send`].
+///
+/// The thread exits when all clones of the pool are dropped, canceling any
+/// operations that have not finished.
+#[derive(Clone)]
+pub struct LocalPool {
+  jobs: channel::Sender<Job>,
+}
+
+/// A job that spawns an operation on the pool thread.
+type Job = Box<dyn FnOnce() + Send>;
+
+impl LocalPool {
+  /// Starts a new pool thread.
+  pub fn new() -> Self {
+    let (jobs, rx) = channel::<Job>();
+
+    std::thread::Builder::new()
+      .name("af-local-pool".into())
+      .spawn(move || {
+        runtime::block_on(async move {
+          while let Some(job) = rx.recv().await {
+            job();
+          }
+        })
+      })
+      .expect("failed to spawn local pool thread");
+
+    Self { jobs }
+  }
+
+  /// Runs an async operation on the pool thread and returns a [`LocalTask`]
+  /// for its output.
+  ///
+  /// The operation is created on the pool thread by calling `factory`, so only
+  /// the factory and the output need to be [`Send`].
+  pub fn spawn<F, T>(&self, factory: impl FnOnce() -> F + Send + 'static) -> LocalTask<T>
+  where
+    F: Future<Output = T> + 'static,
+    T: Send + 'static,
+  {
+    let (tx, rx) = channel();
+
+    self.jobs.send(Box::new(move || {
+      tokio::task::spawn_local(async move {
+        tx.send(factory().await);
+      });
+    }));
+
+    LocalTask { rx }
+  }
+}
+
+impl Default for LocalPool {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// An async operation started with [`LocalPool::spawn()`].
+pub struct LocalTask<T> {
+  rx: channel::Receiver<T>,
+}
+
+impl<T> LocalTask<T> {
+  /// Waits for the operation to finish and returns its output.
+  ///
+  /// If the operation panics or is canceled because the pool was dropped, this
+  /// function panics.
+  pub async fn join(self) -> T {
+    self.rx.recv().await.expect("local pool operation panicked or was canceled")
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::concurrency::cooperative_yield;
+
+  #[async_test]
+  async fn should_run_non_send_ops() {
+    let pool = LocalPool::new();
+
+    let task = pool.spawn(|| async {
+      let value = Rc::new(21);
+
+      cooperative_yield().await;
+
+      (*value * 2, std::thread::current().name().map(String::from))
+    });
+
+    assert_eq!(task.join().await, (42, Some("af-local-pool".into())));
+  }
+}
@@ -4,32 +4,146 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-//! Concurrency runtime plumbing not intended for end users.
+//! Configuration of the async runtime shared by all concurrency scopes.
+
+mod shutdown;
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Release};
 
 use tokio::runtime::{Handle, Runtime};
 use tokio::task::LocalSet;
-pub use tokio::time::Sleep;
+pub(crate) use tokio::time::Sleep;
 
 use super::{future, scope, OnceCell};
 use crate::prelude::*;
 use crate::time::Duration;
-use crate::util::log;
+use crate::util::{self, log};
+
+pub use self::shutdown::shutdown_signal;
+pub(crate) use self::shutdown::signal_latch;
 
 /// A handle to the shared global runtime.
 static HANDLE: OnceCell<Handle> = OnceCell::new();
 
+/// The number of worker threads to start the runtime with, or `0` to use the
+/// default.
+static WORKER_THREADS: AtomicUsize = AtomicUsize::new(0);
+
 thread_local! {
   /// A thread-specific flag indicating whether a [`LocalSet`] is available.
-  static HAS_LOCAL_SET: Cell<bool> = Cell::new(false);
+  static HAS_LOCAL_SET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Configures and runs the async runtime.
+///
+/// Options that are not set use the same defaults as the `main` attribute.
+#[derive(Clone, Debug, Default)]
+pub struct Builder {
+  log_target: Option<&'static str>,
+  thread_name_prefix: Option<String>,
+  worker_threads: Option<usize>,
+}
+
+impl Builder {
+  /// Creates a new builder with the default options.
+  pub fn new() -> Self {
+    default()
+  }
+
+  /// Sets the log target used to report an error returned from the main
+  /// scope.
+  ///
+  /// This is typically `module_path!()` in the caller. If it is not set, the
+  /// target `main` is used.
+  pub fn log_target(mut self, target: &'static str) -> Self {
+    self.log_target = Some(target);
+    self
+  }
+
+  /// Starts the runtime and runs an async operation as the main scope, then
+  /// exits the process.
+  ///
+  /// This function panics if a runtime was already started.
+  pub fn run<O, F>(self, op: F) -> !
+  where
+    O: scope::IntoOutput + 'static,
+    F: Future<Output = O> + 'static,
+  {
+    // Start a tokio runtime.
+
+    let runtime = self.start();
+
+    if HANDLE.set(runtime.handle().clone()).is_err() {
+      panic!("a runtime was already started");
+    }
+
+    // Run the main function in a concurrency scope with a local set for fibers.
+
+    let result = with_local_set(|local| local.block_on(&runtime, scope::run(op)));
+
+    if let Err(err) = &result {
+      error!(target: self.log_target.unwrap_or("main"), "Main thread {}", err);
+    }
+
+    // Tear down global values, then ensure all log messages have been written.
+
+    util::teardown();
+
+    runtime.block_on(log::flush());
+
+    // Exit with the appropriate code.
+
+    let code = process::get_exit_code();
+
+    if code == 0 && result.is_err() {
+      process::exit(i8::MIN);
+    }
+
+    process::exit(code);
+  }
+
+  /// Starts a new tokio runtime with the configured options.
+  fn start(&self) -> Runtime {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+
+    builder.worker_threads(self.worker_threads.unwrap_or_else(default_worker_threads));
+
+    if let Some(prefix) = self.thread_name_prefix.clone() {
+      let next_id = AtomicUsize::new(1);
+
+      builder.thread_name_fn(move || format!("{}-{}", prefix, next_id.fetch_add(1, AcqRel)));
+    }
+
+    builder.enable_all().build().expect("failed to start tokio runtime")
+  }
+
+  /// Sets the prefix of the names of runtime threads.
+  ///
+  /// Each thread is named with the prefix followed by a dash and a number.
+  pub fn thread_name_prefix(mut self, prefix: impl Into<String>) -> Self {
+    self.thread_name_prefix = Some(prefix.into());
+    self
+  }
+
+  /// Sets the number of worker threads in the shared global thread pool.
+  ///
+  /// If this is not set, the value passed to [`set_worker_threads()`] is used.
+  pub fn worker_threads(mut self, n: usize) -> Self {
+    assert!(n > 0, "the number of worker threads must be greater than zero");
+
+    self.worker_threads = Some(n);
+    self
+  }
 }
 
 /// Blocks the current thread to run an async operation.
-pub fn block_on<O>(op: impl Future<Output = O>) -> O {
+pub(crate) fn block_on<O>(op: impl Future<Output = O>) -> O {
   with_local_set(|local| handle().block_on(local.run_until(op)))
 }
 
 /// Returns `true` if [`spawn_local()`] can be used in the current context.
-pub fn can_spawn_local() -> bool {
+pub(crate) fn can_spawn_local() -> bool {
   HAS_LOCAL_SET.with(|cell| cell.get())
 }
 
@@ -51,7 +165,7 @@ fn with_local_set<O>(closure: impl FnOnce(LocalSet) -> O) -> O {
 /// work.
 fn handle() -> &'static Handle {
   HANDLE.get_or_init(|| {
-    let runtime = Runtime::new().expect("failed to start tokio runtime");
+    let runtime = Builder::new().start();
     let handle = runtime.handle().clone();
 
     std::thread::spawn(move || runtime.block_on(future::never()));
@@ -61,44 +175,29 @@ fn handle() -> &'static Handle {
 }
 
 /// Runs an async operation as the main scope and then exits the process.
+#[doc(hidden)]
 pub fn run<O, F>(module_path: &'static str, op: F) -> !
 where
   O: scope::IntoOutput + 'static,
   F: Future<Output = O> + 'static,
 {
-  // Start a tokio runtime.
-
-  let runtime = Runtime::new().expect("failed to start tokio runtime");
-
-  if HANDLE.set(runtime.handle().clone()).is_err() {
-    panic!("a runtime was already started");
-  }
-
-  // Run the main function in a concurrency scope with a local set for fibers.
-
-  let result = with_local_set(|local| local.block_on(&runtime, scope::run(op)));
-
-  if let Err(err) = &result {
-    error!(target: module_path, "Main thread {}", err);
-  }
-
-  // Ensure all log messages have been written.
-
-  runtime.block_on(log::flush());
-
-  // Exit with the appropriate code.
-
-  let code = process::get_exit_code();
-
-  if code == 0 && result.is_err() {
-    process::exit(i8::MIN);
-  }
+  Builder::new().log_target(module_path).run(op)
+}
 
-  process::exit(code);
+/// Sets the number of worker threads in the shared global thread pool.
+///
+/// This function must be called before the runtime starts, typically at the
+/// beginning of `main`. It has no effect afterward. If it is never called, the
+/// number of worker threads is read from the `AF_WORKER_THREADS` environment
+/// variable, or defaults to the number of CPU cores.
+pub fn set_worker_threads(n: usize) {
+  assert!(n > 0, "the number of worker threads must be greater than zero");
+
+  WORKER_THREADS.store(n, Release);
 }
 
 /// Waits for a duration of time to elapse.
-pub fn sleep(duration: Duration) -> Sleep {
+pub(crate) fn sleep(duration: Duration) -> Sleep {
   let duration = duration.to_std();
 
   std::panic::catch_unwind(|| tokio::time::sleep(duration)).unwrap_or_else(|_| {
@@ -109,12 +208,22 @@ pub fn sleep(duration: Duration) -> Sleep {
 }
 
 /// Spawns an async operation onto the shared global thread pool.
-pub fn spawn(op: impl Future<Output = ()> + Send + 'static) -> AsyncOp {
+pub(crate) fn spawn(op: impl Future<Output = ()> + Send + 'static) -> AsyncOp {
   AsyncOp(handle().spawn(op))
 }
 
+/// Runs a blocking closure on a dedicated thread pool.
+pub(crate) fn spawn_blocking<T>(
+  closure: impl FnOnce() -> T + Send + 'static,
+) -> tokio::task::JoinHandle<T>
+where
+  T: Send + 'static,
+{
+  handle().spawn_blocking(closure)
+}
+
 /// Spawns an async operation onto the current thread.
-pub fn spawn_local(op: impl Future<Output = ()> + 'static) -> AsyncOp {
+pub(crate) fn spawn_local(op: impl Future<Output = ()> + 'static) -> AsyncOp {
   assert!(can_spawn_local(), "spawn_local() is not allowed in this context");
   AsyncOp(tokio::task::spawn_local(op))
 }
@@ -122,10 +231,98 @@ pub fn spawn_local(op: impl Future<Output = ()> + 'static) -> AsyncOp {
 /// A spawned async operation.
 ///
 /// The operation is canceled when this structure is dropped.
-pub struct AsyncOp(tokio::task::JoinHandle<()>);
+pub(crate) struct AsyncOp(tokio::task::JoinHandle<()>);
 
 impl Drop for AsyncOp {
   fn drop(&mut self) {
     self.0.abort();
   }
 }
+
+/// Returns the number of worker threads in the shared global thread pool.
+///
+/// If no runtime is started, this will start one.
+pub fn worker_count() -> usize {
+  handle().metrics().num_workers()
+}
+
+/// Returns the number of worker threads to start the runtime with if the
+/// [`Builder`] does not specify one.
+fn default_worker_threads() -> usize {
+  let env_value = process::env::get("AF_WORKER_THREADS").ok();
+
+  worker_threads_from(WORKER_THREADS.load(Acquire), env_value.as_deref())
+}
+
+/// Returns the number of worker threads to use given the number passed to
+/// [`set_worker_threads()`], or zero if it was not called, and the value of the
+/// `AF_WORKER_THREADS` environment variable.
+///
+/// If neither is set, this returns the number of CPUs. An invalid environment
+/// variable value is ignored with a warning.
+fn worker_threads_from(configured: usize, env_value: Option<&str>) -> usize {
+  if configured > 0 {
+    return configured;
+  }
+
+  if let Some(value) = env_value {
+    match value.parse() {
+      Ok(n) if n > 0 => return n,
+      _ => warn!("Ignoring invalid AF_WORKER_THREADS value `{}`.", value),
+    }
+  }
+
+  num_cpus::get()
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_start_configured_worker_threads() {
+    let runtime = Builder::new().worker_threads(3).start();
+
+    assert_eq!(runtime.handle().metrics().num_workers(), 3);
+  }
+
+  #[test]
+  fn should_name_worker_threads() {
+    let runtime = Builder::new().worker_threads(2).thread_name_prefix("test-worker").start();
+
+    assert_eq!(runtime.handle().metrics().num_workers(), 2);
+
+    let tasks: Vec<_> = (0..8)
+      .map(|_| runtime.spawn(async { std::thread::current().name().map(String::from) }))
+      .collect();
+
+    for task in tasks {
+      let name = runtime.block_on(task).unwrap().unwrap();
+
+      assert!(name.starts_with("test-worker-"), "unexpected thread name `{}`", name);
+    }
+  }
+
+  #[test]
+  fn should_prefer_configured_worker_threads() {
+    assert_eq!(worker_threads_from(4, None), 4);
+    assert_eq!(worker_threads_from(4, Some("2")), 4);
+  }
+
+  #[test]
+  fn should_parse_worker_threads_from_env() {
+    assert_eq!(worker_threads_from(0, Some("2")), 2);
+    assert_eq!(worker_threads_from(0, Some("16")), 16);
+  }
+
+  #[test]
+  fn should_ignore_invalid_worker_threads_from_env() {
+    assert_eq!(worker_threads_from(0, None), num_cpus::get());
+    assert_eq!(worker_threads_from(0, Some("0")), num_cpus::get());
+    assert_eq!(worker_threads_from(0, Some("-1")), num_cpus::get());
+    assert_eq!(worker_threads_from(0, Some(" 2 ")), num_cpus::get());
+    assert_eq!(worker_threads_from(0, Some("lots")), num_cpus::get());
+  }
+}
@@ -0,0 +1,185 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::concurrency::{future, Latch, Lazy};
+use crate::prelude::*;
+
+/// A latch that is set when the process receives a shutdown signal.
+static SIGNALED: Lazy<Latch<()>> = Lazy::new(|| {
+  let latch = Latch::new();
+  let signaled = latch.clone();
+  let handle = super::handle();
+
+  // Listen for signals before returning so that none are missed.
+
+  let mut signals = {
+    let _guard = handle.enter();
+
+    sys::Signals::listen()
+  };
+
+  handle.spawn(async move {
+    signals.recv().await;
+    signaled.set(());
+
+    signals.recv().await;
+    std::process::exit(130);
+  });
+
+  latch
+});
+
+/// Waits for the process to receive a request to shut down.
+///
+/// On Unix platforms, this is the `SIGINT` or `SIGTERM` signal. On Windows,
+/// this is Ctrl-C or Ctrl-Break in the console.
+///
+/// Signal handlers are installed the first time this function is called and
+/// stay in place for the life of the process, so a signal received before the
+/// returned future is polled is not missed. A second signal exits the process
+/// immediately with exit code 130, even if shutdown is still in progress.
+pub fn shutdown_signal() -> impl Future<Output = ()> {
+  SIGNALED.get()
+}
+
+/// Returns the latch that is set when the process receives a shutdown signal,
+/// installing signal handlers if needed.
+pub(crate) fn signal_latch() -> Latch<()> {
+  SIGNALED.clone()
+}
+
+/// Unix signal handling.
+#[cfg(unix)]
+mod sys {
+  use tokio::signal::unix::{signal, Signal, SignalKind};
+
+  use super::future;
+
+  /// Listeners for `SIGINT` and `SIGTERM`.
+  pub struct Signals {
+    interrupt: Signal,
+    terminate: Signal,
+  }
+
+  impl Signals {
+    /// Starts listening for signals.
+    pub fn listen() -> Self {
+      let listen = |kind| signal(kind).expect("failed to listen for shutdown signals");
+
+      Self {
+        interrupt: listen(SignalKind::interrupt()),
+        terminate: listen(SignalKind::terminate()),
+      }
+    }
+
+    /// Waits for the next signal.
+    pub async fn recv(&mut self) {
+      let Self { interrupt, terminate } = self;
+
+      future::race(
+        async {
+          interrupt.recv().await;
+        },
+        async {
+          terminate.recv().await;
+        },
+      )
+      .await
+    }
+  }
+}
+
+/// Windows console control handling.
+#[cfg(windows)]
+mod sys {
+  use tokio::signal::windows::{ctrl_break, ctrl_c, CtrlBreak, CtrlC};
+
+  use super::future;
+
+  /// Listeners for Ctrl-C and Ctrl-Break.
+  pub struct Signals {
+    ctrl_c: CtrlC,
+    ctrl_break: CtrlBreak,
+  }
+
+  impl Signals {
+    /// Starts listening for console control events.
+    pub fn listen() -> Self {
+      Self {
+        ctrl_c: ctrl_c().expect("failed to listen for Ctrl-C"),
+        ctrl_break: ctrl_break().expect("failed to listen for Ctrl-Break"),
+      }
+    }
+
+    /// Waits for the next console control event.
+    pub async fn recv(&mut self) {
+      let Self { ctrl_c, ctrl_break } = self;
+
+      future::race(
+        async {
+          ctrl_c.recv().await;
+        },
+        async {
+          ctrl_break.recv().await;
+        },
+      )
+      .await
+    }
+  }
+}
+
+// Tests
+
+#[cfg(all(test, unix))]
+mod tests {
+  use std::io::{BufRead, BufReader};
+  use std::process::{Command, Stdio};
+
+  use super::*;
+
+  /// An environment variable set when the test binary is re-run as a child
+  /// process to receive signals.
+  const CHILD_VAR: &str = "AF_LIB_SHUTDOWN_TEST_CHILD";
+
+  #[async_test]
+  async fn should_exit_on_second_signal() {
+    // Signals affect the whole process, so run the test binary again as a child
+    // process and send signals to it instead.
+
+    if std::env::var_os(CHILD_VAR).is_some() {
+      let signal = shutdown_signal();
+
+      eprintln!("ready");
+
+      signal.await;
+
+      eprintln!("signaled");
+
+      future::never().await;
+    }
+
+    let mut child = Command::new(std::env::current_exe().unwrap())
+      .args(["--exact", "--nocapture", "--test-threads=1"])
+      .arg("concurrency::runtime::shutdown::tests::should_exit_on_second_signal")
+      .env(CHILD_VAR, "1")
+      .stdout(Stdio::null())
+      .stderr(Stdio::piped())
+      .spawn()
+      .unwrap();
+
+    let mut lines = BufReader::new(child.stderr.take().unwrap()).lines().map(Result::unwrap);
+    let mut wait_for = |expected: &str| assert!(lines.any(|line| line == expected), "{}", expected);
+    let terminate = || unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGTERM) };
+
+    wait_for("ready");
+    assert_eq!(terminate(), 0);
+
+    wait_for("signaled");
+    assert_eq!(terminate(), 0);
+
+    assert_eq!(child.wait().unwrap().code(), Some(130));
+  }
+}
@@ -146,6 +146,7 @@ where
 
 /// An error returned from a scope.
 #[derive(From)]
+#[allow(clippy::enum_variant_names)]
 pub enum Error {
   #[from]
   Error(String),
@@ -247,6 +248,12 @@ impl Scope {
   {
     let scope = Arc::downgrade(self);
 
+    // Give the child its own copy of the span fields so that spans in the
+    // parent and child do not interfere when they run on the same thread.
+
+    #[cfg(feature = "logger")]
+    let op = crate::util::log::inherit_span_fields(op);
+
     async move {
       let result = run(op).await;
 
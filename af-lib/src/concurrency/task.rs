@@ -7,10 +7,23 @@
 //! Run operations concurrently on a shared, global thread pool by starting
 //! them on separate tasks.
 
-use super::{runtime, scope};
+use super::future::{ready, Context, Poll};
+use super::{cooperative_yield, runtime, scope};
 use crate::prelude::*;
 use crate::util::SharedStr;
 
+/// Runs a blocking closure on a dedicated thread pool and returns a [`Task`]
+/// for its output.
+///
+/// Use this for synchronous I/O or heavy computation that would otherwise stall
+/// a worker thread of the async executor.
+pub fn spawn_blocking<T>(closure: impl FnOnce() -> T + Send + 'static) -> Task<T>
+where
+  T: Send + 'static,
+{
+  Task(runtime::spawn_blocking(closure))
+}
+
 /// Starts a concurrency scope on a child task that runs on the global thread
 /// pool.
 #[track_caller]
@@ -20,6 +33,7 @@ where
 {
   start_as("", op)
 }
+
 /// Starts a named concurrency scope on a child task that runs on the global
 /// thread pool.
 #[track_caller]
@@ -34,6 +48,45 @@ where
   parent.insert_child(id, child);
 }
 
+/// Increments a counter and yields once to pending concurrent operations each
+/// time it reaches a multiple of `every`.
+///
+/// Call this in long-running loops so that they do not starve other operations
+/// on the same thread.
+pub async fn yield_periodically(counter: &mut usize, every: usize) {
+  *counter = counter.wrapping_add(1);
+
+  if every > 0 && counter.is_multiple_of(every) {
+    cooperative_yield().await;
+  }
+}
+
+/// A blocking operation started with [`spawn_blocking()`].
+///
+/// Awaiting the task waits for the operation to finish and returns its output.
+/// If the operation panics, the panic is resumed in the awaiting context.
+/// Dropping the task does not cancel the operation.
+pub struct Task<T>(tokio::task::JoinHandle<T>);
+
+impl<T> Task<T> {
+  /// Waits for the operation to finish and returns its output.
+  pub async fn join(self) -> T {
+    self.await
+  }
+}
+
+impl<T> Future for Task<T> {
+  type Output = T;
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+    match ready!(Pin::new(&mut self.0).poll(cx)) {
+      Ok(output) => Poll::Ready(output),
+      Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+      Err(err) => panic!("blocking task failed: {}", err),
+    }
+  }
+}
+
 // Tests
 
 #[cfg(test)]
@@ -43,6 +96,7 @@ mod tests {
 
   use super::*;
   use crate::concurrency::join;
+  use crate::time::{Duration, Instant};
 
   #[async_test]
   async fn should_work() {
@@ -71,10 +125,43 @@ mod tests {
   #[async_test]
   #[should_panic]
   async fn should_propagate_panics() {
-    start(async {
+    start::<()>(async {
       panic!("oh no!");
     });
 
     join().await;
   }
+
+  #[async_test]
+  async fn spawn_blocking_should_not_block_executor() {
+    let started = Instant::now();
+
+    let task = spawn_blocking(|| {
+      std::thread::sleep(std::time::Duration::from_millis(50));
+      42
+    });
+
+    Duration::milliseconds(5).elapsed().await;
+
+    assert!(started.duration_since() < Duration::milliseconds(50));
+    assert_eq!(task.join().await, 42);
+    assert!(started.duration_since() >= Duration::milliseconds(50));
+  }
+
+  #[async_test]
+  async fn should_yield_periodically() {
+    let mut counter = 0;
+    let mut yields = Vec::new();
+
+    for i in 1..=10 {
+      let yielded = futures_lite::future::poll_once(yield_periodically(&mut counter, 3)).await;
+
+      if yielded.is_none() {
+        yields.push(i);
+      }
+    }
+
+    assert_eq!(counter, 10);
+    assert_eq!(yields, [3, 6, 9]);
+  }
 }
@@ -98,7 +98,7 @@ mod tests {
   #[async_test]
   #[should_panic]
   async fn should_propagate_panics() {
-    start(async {
+    start::<()>(async {
       panic!("oh no!");
     });
 
@@ -8,8 +8,28 @@
 
 pub mod path;
 
+use std::collections::VecDeque;
 use std::io;
 
+use crate::concurrency::task;
+use crate::prelude::*;
+use crate::util::stream::{self, Stream};
+
+/// The number of entries [`read_dir()`] reads on the blocking thread pool at a
+/// time.
+const READ_DIR_BATCH_SIZE: usize = 64;
+
+/// Creates a directory and all of its missing parent directories.
+///
+/// If the directory already exists, this function does nothing. The directories
+/// are created on a dedicated thread pool so that the async executor is not
+/// blocked.
+pub async fn create_dir_all(path: impl AsRef<str>) -> Result<(), io::Error> {
+  let path = path.as_ref().to_owned();
+
+  task::spawn_blocking(move || std::fs::create_dir_all(path)).await
+}
+
 /// Checks whether a path exists.
 pub fn exists(path: impl AsRef<str>) -> Result<bool, io::Error> {
   match std::fs::metadata(path.as_ref()) {
@@ -36,3 +56,171 @@ pub fn is_file(path: impl AsRef<str>) -> Result<bool, io::Error> {
     Err(err) => Err(err),
   }
 }
+
+/// Reads the entries of a directory.
+///
+/// The directory is read in batches on a dedicated thread pool so that the
+/// async executor is not blocked. The returned stream yields an error for any
+/// entry that cannot be read or whose name contains non-Unicode characters.
+pub async fn read_dir(
+  path: impl AsRef<str>,
+) -> Result<impl Stream<Item = Result<DirEntry, io::Error>>, io::Error> {
+  let path = path.as_ref().to_owned();
+  let iter = task::spawn_blocking(move || std::fs::read_dir(path)).await?;
+
+  Ok(stream::unfold((Some(iter), VecDeque::new()), |(mut iter, mut batch)| async move {
+    if batch.is_empty() {
+      let mut dir = iter.take()?;
+
+      let (dir, next_batch) = task::spawn_blocking(move || {
+        let batch: VecDeque<_> = dir
+          .by_ref()
+          .take(READ_DIR_BATCH_SIZE)
+          .map(|entry| entry.and_then(DirEntry::from_std))
+          .collect();
+
+        (dir, batch)
+      })
+      .await;
+
+      // A partial batch means the directory has no more entries.
+
+      if next_batch.len() == READ_DIR_BATCH_SIZE {
+        iter = Some(dir);
+      }
+
+      batch = next_batch;
+    }
+
+    let entry = batch.pop_front()?;
+
+    Some((entry, (iter, batch)))
+  }))
+}
+
+/// Removes a directory and all of its contents.
+///
+/// The directory is removed on a dedicated thread pool so that the async
+/// executor is not blocked.
+pub async fn remove_dir_all(path: impl AsRef<str>) -> Result<(), io::Error> {
+  let path = path.as_ref().to_owned();
+
+  task::spawn_blocking(move || std::fs::remove_dir_all(path)).await
+}
+
+/// An entry in a directory returned from [`read_dir()`].
+#[derive(Debug)]
+pub struct DirEntry {
+  file_type: std::fs::FileType,
+  name: String,
+  path: String,
+}
+
+impl DirEntry {
+  /// Converts a `std::fs::DirEntry` into a `DirEntry`.
+  fn from_std(entry: std::fs::DirEntry) -> Result<Self, io::Error> {
+    let not_unicode = |_| io::Error::new(io::ErrorKind::InvalidData, "non-Unicode path");
+
+    Ok(Self {
+      file_type: entry.file_type()?,
+      name: entry.file_name().into_string().map_err(not_unicode)?,
+      path: entry.path().into_os_string().into_string().map_err(not_unicode)?,
+    })
+  }
+
+  /// Returns `true` if the entry is a directory.
+  pub fn is_dir(&self) -> bool {
+    self.file_type.is_dir()
+  }
+
+  /// Returns `true` if the entry is a file.
+  pub fn is_file(&self) -> bool {
+    self.file_type.is_file()
+  }
+
+  /// Returns the file name of the entry.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns the full path to the entry.
+  pub fn path(&self) -> &str {
+    &self.path
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::util::stream::StreamExt as _;
+  use crate::util::Uuid;
+
+  #[async_test]
+  async fn should_create_and_remove_dirs() {
+    let mut root: String = std::env::temp_dir().to_str().unwrap().into();
+
+    path::append(&mut root, &Uuid::new().to_string());
+
+    let nested = path::join(&root, "a/b/c");
+
+    create_dir_all(&nested).await.unwrap();
+    create_dir_all(&nested).await.unwrap();
+
+    assert!(is_dir(&nested).unwrap());
+
+    remove_dir_all(&root).await.unwrap();
+
+    assert!(!exists(&root).unwrap());
+  }
+
+  #[async_test]
+  async fn read_dir_should_stream_entries() {
+    let mut dir: String = std::env::temp_dir().to_str().unwrap().into();
+
+    path::append(&mut dir, &Uuid::new().to_string());
+
+    std::fs::create_dir(&dir).unwrap();
+    std::fs::write(path::join(&dir, "b.txt").as_ref(), "b").unwrap();
+    std::fs::write(path::join(&dir, "a.txt").as_ref(), "a").unwrap();
+    std::fs::create_dir(path::join(&dir, "c").as_ref()).unwrap();
+
+    let entries = read_dir(&dir).await.unwrap().try_collect::<_, _, Vec<_>>().await;
+    let mut entries = entries.unwrap();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    entries.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let names: Vec<_> = entries.iter().map(|e| (e.name(), e.is_file(), e.is_dir())).collect();
+
+    assert_eq!(names, [("a.txt", true, false), ("b.txt", true, false), ("c", false, true)]);
+    assert_eq!(entries[0].path(), path::join(&dir, "a.txt"));
+  }
+
+  #[async_test]
+  async fn read_dir_should_stream_entries_across_batches() {
+    let mut dir: String = std::env::temp_dir().to_str().unwrap().into();
+
+    path::append(&mut dir, &Uuid::new().to_string());
+
+    std::fs::create_dir(&dir).unwrap();
+
+    for i in 0..READ_DIR_BATCH_SIZE * 2 + 1 {
+      std::fs::write(path::join(&dir, &format!("{}.txt", i)).as_ref(), "").unwrap();
+    }
+
+    let entries = read_dir(&dir).await.unwrap().try_collect::<_, _, Vec<_>>().await;
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let mut names: Vec<_> = entries.unwrap().into_iter().map(|e| e.name).collect();
+
+    names.sort_by_key(|name| name.trim_end_matches(".txt").parse::<usize>().unwrap());
+
+    let expected: Vec<_> = (0..READ_DIR_BATCH_SIZE * 2 + 1).map(|i| format!("{}.txt", i)).collect();
+
+    assert_eq!(names, expected);
+  }
+}
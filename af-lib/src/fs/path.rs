@@ -28,7 +28,7 @@ pub fn append(base: &mut String, relative: &str) {
     return;
   }
 
-  match base.chars().rev().next() {
+  match base.chars().next_back() {
     None => base.replace_range(.., relative),
     Some(c) if is_separator(c) => base.push_str(relative),
     Some(_) => {
@@ -39,6 +39,48 @@ pub fn append(base: &mut String, relative: &str) {
   }
 }
 
+/// Returns the deepest common ancestor of the given paths.
+///
+/// Each path is normalized first, and paths are compared component by
+/// component, so `/a/bc` and `/a/bd` share `/a` rather than `/a/b`. If the
+/// paths have nothing in common or the slice is empty, this function returns
+/// an empty string.
+///
+/// Paths are compared without accessing the file system, so:
+///
+/// - Unrelated absolute paths such as `/a/b` and `/c/d` share the root `/`.
+/// - A single path is returned unchanged, even if it names a file, because
+///   this function cannot tell files from directories. Use [`parent()`] on the
+///   result to get the directory of a single file.
+pub fn common_prefix(paths: &[&str]) -> String {
+  let paths: Vec<_> = paths.iter().map(|path| normalized(*path)).collect();
+  let mut components = paths.iter().map(|path| as_std(path).components());
+
+  let mut prefix: Vec<_> = match components.next() {
+    Some(first) => first.collect(),
+    None => return String::new(),
+  };
+
+  for path in components {
+    let common = prefix.iter().zip(path).take_while(|(a, b)| **a == *b).count();
+
+    prefix.truncate(common);
+  }
+
+  let prefix: std::path::PathBuf = prefix.into_iter().collect();
+
+  prefix.into_os_string().into_string().expect("path is not unicode")
+}
+
+/// Returns the extension of the last component of the path.
+///
+/// The extension is the portion of the last component after its final `.`. If
+/// the last component has no `.` or is a dotfile such as `.gitignore`, this
+/// function returns `None`.
+pub fn extension(path: &str) -> Option<&str> {
+  as_std(path).extension()?.to_str()
+}
+
 /// Returns `true` if the given path is absolute.
 pub fn is_absolute(path: &str) -> bool {
   as_std(path).is_absolute()
@@ -110,7 +152,7 @@ pub fn parent(path: &str) -> Option<&str> {
 /// If the path is a root or empty path, this function does nothing and returns
 /// `None`.
 pub fn pop(path: &mut String) -> Option<String> {
-  let split_at = parent(&path)?.len();
+  let split_at = parent(path)?.len();
   let lead_seps = path[split_at..].chars().take_while(|c| is_separator(*c)).count();
   let trail_seps = path.chars().rev().take_while(|c| is_separator(*c)).count();
   let mut last = path.split_off(split_at + lead_seps);
@@ -121,9 +163,58 @@ pub fn pop(path: &mut String) -> Option<String> {
   Some(last)
 }
 
+/// Returns a relative path from `base` to `path`.
+///
+/// Both paths are normalized first. The result uses `..` components to leave
+/// `base` as needed, and is `.` if the paths are equal. If the paths do not
+/// share a root or prefix, for example if only one of them is absolute, this
+/// function returns `None`.
+pub fn relative_to(path: &str, base: &str) -> Option<String> {
+  use std::path::Component;
+
+  let path = normalized(path);
+  let base = normalized(base);
+
+  if is_absolute(&path) != is_absolute(&base) {
+    return None;
+  }
+
+  let path: Vec<_> = as_std(&path).components().collect();
+  let base: Vec<_> = as_std(&base).components().collect();
+  let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+
+  // Paths with different roots or prefixes have no relative path.
+
+  let is_root =
+    |c: Option<&Component>| matches!(c, Some(Component::Prefix(_) | Component::RootDir));
+
+  if common == 0 && (is_root(path.first()) || is_root(base.first())) {
+    return None;
+  }
+
+  // Leave each remaining component of the base and then enter each remaining
+  // component of the path.
+
+  let mut output = String::new();
+
+  for _ in common..base.len() {
+    append(&mut output, "..");
+  }
+
+  for component in &path[common..] {
+    append(&mut output, component.as_os_str().to_str()?);
+  }
+
+  if output.is_empty() {
+    output.push('.');
+  }
+
+  Some(output)
+}
+
 /// Resolves the given path into an absolute, normalized path.
 pub fn resolve(path: &mut String) -> Result<(), process::WorkingPathError> {
-  if !is_absolute(&path) {
+  if !is_absolute(path) {
     let mut buf = process::working_path()?;
 
     mem::swap(path, &mut buf);
@@ -153,12 +244,22 @@ pub fn starts_with(path: &str, prefix: &str) -> bool {
   as_std(path).starts_with(prefix)
 }
 
+/// Returns the given path with its extension replaced by `ext`.
+///
+/// If the path has no extension, `ext` is appended. If `ext` is empty, the
+/// existing extension is removed.
+pub fn with_extension(path: &str, ext: &str) -> String {
+  let path = as_std(path).with_extension(ext);
+
+  path.into_os_string().into_string().expect("path is not unicode")
+}
+
 /// Returns the given path with a trailing separator if it does not already
 /// have one.
 pub fn with_trailing_sep<'a>(path: impl PathLike<'a>) -> Cow<'a, str> {
   let mut path = path.to_cow();
 
-  match path.chars().rev().next() {
+  match path.chars().next_back() {
     Some(c) if is_separator(c) => path,
 
     _ => {
@@ -251,3 +352,70 @@ impl<'a> PathLike<'a> for &'a std::path::PathBuf {
     self.to_string_lossy().to_cow()
   }
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_get_common_prefix() {
+    assert_eq!(common_prefix(&["/a/b/c/d", "/a/b/c", "/a/b/e/../c/f.txt"]), "/a/b/c");
+    assert_eq!(common_prefix(&["/a/bc", "/a/bd"]), "/a");
+    assert_eq!(common_prefix(&[]), "");
+  }
+
+  #[test]
+  fn common_prefix_should_return_single_path_unchanged() {
+    assert_eq!(common_prefix(&["/a/b"]), "/a/b");
+    assert_eq!(common_prefix(&["/a/b/file.txt"]), "/a/b/file.txt");
+    assert_eq!(common_prefix(&["/a/./b/"]), "/a/b");
+  }
+
+  #[test]
+  fn common_prefix_should_return_root_of_unrelated_absolute_paths() {
+    assert_eq!(common_prefix(&["/a/b", "/c/d"]), "/");
+    assert_eq!(common_prefix(&["/a", "/b", "/c/d/e"]), "/");
+  }
+
+  #[test]
+  fn should_not_get_common_prefix_of_disjoint_paths() {
+    assert_eq!(common_prefix(&["/a/b", "a/b"]), "");
+    assert_eq!(common_prefix(&["a/b", "c/d"]), "");
+  }
+
+  #[test]
+  fn should_get_extension() {
+    assert_eq!(extension("a/b.txt"), Some("txt"));
+    assert_eq!(extension("a/b.tar.gz"), Some("gz"));
+    assert_eq!(extension("a/b"), None);
+    assert_eq!(extension(".bashrc"), None);
+  }
+
+  #[test]
+  fn should_get_relative_path_between_siblings() {
+    assert_eq!(relative_to("/a/b/c", "/a/b/d").as_deref(), Some("../c"));
+    assert_eq!(relative_to("/a/x/y", "/a/b/c").as_deref(), Some("../../x/y"));
+  }
+
+  #[test]
+  fn should_get_relative_path_between_nested_dirs() {
+    assert_eq!(relative_to("/a/b/c/d", "/a/b").as_deref(), Some("c/d"));
+    assert_eq!(relative_to("/a", "/a/b/c").as_deref(), Some("../.."));
+    assert_eq!(relative_to("/a/./b/", "/a/b").as_deref(), Some("."));
+  }
+
+  #[test]
+  fn should_not_get_relative_path_between_different_roots() {
+    assert_eq!(relative_to("/a/b", "a/b"), None);
+    assert_eq!(relative_to("a/b", "/a"), None);
+  }
+
+  #[test]
+  fn should_replace_extension() {
+    assert_eq!(with_extension("a.txt", "md"), "a.md");
+    assert_eq!(with_extension("a/b", "md"), "a/b.md");
+    assert_eq!(with_extension("a/b.txt", ""), "a/b");
+  }
+}
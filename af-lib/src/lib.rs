@@ -33,3 +33,77 @@ pub mod time;
 pub mod util;
 
 pub use af_macros::{async_test, main};
+
+/// An attribute macro that turns an `async fn` into a standard `#[test]`.
+///
+/// This is an alias of [`async_test`] for use as `#[af::test]`. The test body
+/// runs on the shared runtime, so it can be mixed freely with synchronous tests
+/// discovered by `cargo test`.
+pub use af_macros::async_test as test;
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use std::process::{Command, Stdio};
+
+  use crate::prelude::*;
+  use crate::time::{Duration, Instant};
+  use crate::util::{failure, Failure};
+
+  /// An environment variable set when the test binary is re-run as a child
+  /// process to run a `main` function.
+  const CHILD_VAR: &str = "AF_LIB_MAIN_TEST_CHILD";
+
+  /// A `main` function that fails with a chain of errors.
+  mod failing {
+    use super::*;
+
+    #[derive(Debug, Error)]
+    #[error("config error")]
+    struct ConfigError(#[source] std::io::Error);
+
+    #[crate::main]
+    pub async fn main() -> Result<(), Failure> {
+      let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing file");
+      let inner = failure!(ConfigError(io), "inner failure");
+
+      Err(Failure::new("outer.rs", 1, "outer failure", Some(inner)))
+    }
+  }
+
+  #[test]
+  fn main_should_print_full_error_chain() {
+    // The main function exits the process, so run the test binary again as a
+    // child process to call it.
+
+    if std::env::var_os(CHILD_VAR).is_some() {
+      failing::main();
+    }
+
+    let output = Command::new(std::env::current_exe().unwrap())
+      .args(["--exact", "--nocapture", "--test-threads=1"])
+      .arg("tests::main_should_print_full_error_chain")
+      .env(CHILD_VAR, "1")
+      .stdin(Stdio::null())
+      .output()
+      .unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+
+    for level in ["outer failure", "inner failure", "config error", "missing file"] {
+      assert!(stderr.contains(level), "missing `{}` in:\n{}", level, stderr);
+    }
+  }
+
+  #[crate::test]
+  async fn test_attribute_should_run_async_tests() {
+    let start = Instant::now();
+
+    Duration::milliseconds(10).elapsed().await;
+
+    assert!(start.duration_since() >= Duration::milliseconds(10));
+  }
+}
@@ -7,6 +7,10 @@
 //! Numerical types and utilities.
 
 mod float;
+mod histogram;
+mod integer;
+mod stats;
+mod vector;
 
 pub use num_traits::identities::{one, zero, One, Zero};
 pub use num_traits::AsPrimitive;
@@ -14,6 +18,10 @@ use num_traits::{NumAssignOps, NumOps};
 use rand::distributions::uniform::SampleUniform;
 
 pub use self::float::FloatExt;
+pub use self::histogram::Histogram;
+pub use self::integer::{count_ones, gcd, is_power_of_two, lcm, next_power_of_two, Integer};
+pub use self::stats::Stats;
+pub use self::vector::{Vec2, Vec3};
 
 /// A trait for types that implement all the basic operations of a number.
 ///
@@ -30,3 +38,104 @@ impl<T> Number for T where
   T: PartialOrd + PartialEq + Zero + One + NumOps + NumAssignOps + SampleUniform
 {
 }
+
+/// Linearly interpolates between `a` and `b`.
+///
+/// A `t` of `0.0` returns `a` and a `t` of `1.0` returns `b`. Values of `t`
+/// outside of that range extrapolate beyond `a` or `b`.
+pub fn lerp(a: impl AsPrimitive<f64>, b: impl AsPrimitive<f64>, t: impl AsPrimitive<f64>) -> f64 {
+  let a = a.as_();
+
+  a + (b.as_() - a) * t.as_()
+}
+
+/// Maps a value from an input range onto an output range.
+///
+/// The input range may be inverted, in which case `in_min` maps to `out_min`
+/// even though it is the greater number. If the input range has zero width,
+/// this function returns `out_min`.
+pub fn map_range(
+  value: impl AsPrimitive<f64>,
+  in_min: impl AsPrimitive<f64>,
+  in_max: impl AsPrimitive<f64>,
+  out_min: impl AsPrimitive<f64>,
+  out_max: impl AsPrimitive<f64>,
+) -> f64 {
+  let in_min = in_min.as_();
+  let in_width = in_max.as_() - in_min;
+
+  if in_width == 0.0 {
+    return out_min.as_();
+  }
+
+  lerp(out_min, out_max, (value.as_() - in_min) / in_width)
+}
+
+/// Wraps a value into the range `[0, modulus)`.
+///
+/// Unlike the `%` operator, the result is never negative, so this function can
+/// be used to wrap indices and angles around in either direction. The
+/// `modulus` must be positive.
+pub fn wrap<T: Number + Copy>(value: T, modulus: T) -> T {
+  let rem = value % modulus;
+
+  if rem >= T::zero() {
+    return rem;
+  }
+
+  // For floats, adding the modulus to a tiny negative remainder can round up
+  // to the modulus itself, which is outside the range.
+
+  match rem + modulus {
+    wrapped if wrapped >= modulus => T::zero(),
+    wrapped => wrapped,
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn lerp_should_interpolate() {
+    assert_eq!(lerp(10, 20, 0.0), 10.0);
+    assert_eq!(lerp(10, 20, 0.5), 15.0);
+    assert_eq!(lerp(10, 20, 1.0), 20.0);
+  }
+
+  #[test]
+  fn map_range_should_map() {
+    assert_eq!(map_range(0, 0, 10, 100, 200), 100.0);
+    assert_eq!(map_range(5, 0, 10, 100, 200), 150.0);
+    assert_eq!(map_range(10, 0, 10, 100, 200), 200.0);
+  }
+
+  #[test]
+  fn map_range_should_support_inverted_ranges() {
+    assert_eq!(map_range(10, 10, 0, 0.0, 1.0), 0.0);
+    assert_eq!(map_range(2.5, 10, 0, 0.0, 1.0), 0.75);
+    assert_eq!(map_range(0, 10, 0, 0.0, 1.0), 1.0);
+  }
+
+  #[test]
+  fn map_range_should_return_out_min_for_empty_ranges() {
+    assert_eq!(map_range(3, 5, 5, 1.0, 2.0), 1.0);
+  }
+
+  #[test]
+  fn wrap_should_wrap_integers() {
+    assert_eq!(wrap(-1, 5), 4);
+    assert_eq!(wrap(-5, 5), 0);
+    assert_eq!(wrap(7, 5), 2);
+    assert_eq!(wrap(7u8, 5), 2);
+  }
+
+  #[test]
+  fn wrap_should_wrap_floats() {
+    assert_eq!(wrap(-90.0, 360.0), 270.0);
+    assert_eq!(wrap(725.0, 360.0), 5.0);
+    assert_eq!(wrap(-1e-20, 360.0), 0.0);
+  }
+}
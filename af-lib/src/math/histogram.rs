@@ -0,0 +1,143 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+/// A histogram that counts values in buckets to estimate percentiles.
+///
+/// Each bucket counts the values less than or equal to its upper bound and
+/// greater than the bound of the previous bucket. A final overflow bucket
+/// counts values greater than the last bound. Values are not stored, so
+/// percentiles are only as precise as the buckets.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+  bounds: Vec<f64>,
+  counts: Vec<u64>,
+  count: u64,
+  max: f64,
+}
+
+impl Histogram {
+  /// Creates a new histogram with the given bucket upper bounds.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `bounds` is empty or not strictly ascending.
+  pub fn new(bounds: impl IntoIterator<Item = f64>) -> Self {
+    let bounds: Vec<_> = bounds.into_iter().collect();
+
+    assert!(!bounds.is_empty(), "a histogram requires at least one bucket");
+    assert!(bounds.windows(2).all(|w| w[0] < w[1]), "bucket bounds must be strictly ascending");
+
+    Self { counts: vec![0; bounds.len() + 1], bounds, count: 0, max: f64::NAN }
+  }
+
+  /// Creates a new histogram with `buckets` exponentially growing bucket upper
+  /// bounds, starting at `start` and multiplied by `factor` for each bucket.
+  ///
+  /// This is useful for values such as latencies, which span several orders of
+  /// magnitude but only need precision relative to their size.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `buckets` is zero, `start` is not positive, or `factor` is not
+  /// greater than `1.0`.
+  pub fn exponential(start: f64, factor: f64, buckets: usize) -> Self {
+    assert!(start > 0.0, "the first bucket bound must be positive");
+    assert!(factor > 1.0, "the bucket growth factor must be greater than 1.0");
+
+    Self::new((0..buckets).scan(start, |bound, _| {
+      let current = *bound;
+
+      *bound *= factor;
+
+      Some(current)
+    }))
+  }
+
+  /// Returns the number of values recorded.
+  pub fn count(&self) -> u64 {
+    self.count
+  }
+
+  /// Returns an estimate of the value below which `p` percent of recorded
+  /// values fall.
+  ///
+  /// The estimate is the upper bound of the bucket containing the percentile,
+  /// limited to the greatest value recorded. If no values have been recorded,
+  /// this function returns NaN.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `p` is not between `0.0` and `100.0`.
+  pub fn percentile(&self, p: f64) -> f64 {
+    assert!((0.0..=100.0).contains(&p), "percentile must be between 0 and 100");
+
+    if self.count == 0 {
+      return f64::NAN;
+    }
+
+    let rank = ((p / 100.0 * self.count as f64).ceil() as u64).max(1);
+    let mut seen = 0;
+
+    for (bound, count) in self.bounds.iter().zip(&self.counts) {
+      seen += count;
+
+      if seen >= rank {
+        return bound.min(self.max);
+      }
+    }
+
+    self.max
+  }
+
+  /// Records a value.
+  pub fn record(&mut self, value: f64) {
+    let index = self.bounds.partition_point(|bound| *bound < value);
+
+    self.counts[index] += 1;
+    self.count += 1;
+    self.max = self.max.max(value);
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_estimate_percentiles() {
+    let mut histogram = Histogram::exponential(1.0, 2.0, 10);
+
+    for _ in 0..90 {
+      histogram.record(1.5);
+    }
+
+    for _ in 0..9 {
+      histogram.record(20.0);
+    }
+
+    histogram.record(50.0);
+
+    assert_eq!(histogram.count(), 100);
+    assert_eq!(histogram.percentile(50.0), 2.0);
+    assert_eq!(histogram.percentile(90.0), 2.0);
+    assert_eq!(histogram.percentile(99.0), 32.0);
+    assert_eq!(histogram.percentile(100.0), 50.0);
+  }
+
+  #[test]
+  fn should_count_overflowing_values() {
+    let mut histogram = Histogram::new([10.0, 100.0]);
+
+    histogram.record(5.0);
+    histogram.record(1000.0);
+
+    assert_eq!(histogram.percentile(50.0), 10.0);
+    assert_eq!(histogram.percentile(99.0), 1000.0);
+    assert!(Histogram::new([1.0]).percentile(50.0).is_nan());
+  }
+}
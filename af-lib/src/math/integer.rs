@@ -0,0 +1,198 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::convert::TryFrom;
+
+use super::Number;
+
+/// A trait for primitive integer types.
+///
+/// This trait is sealed and implemented for all signed and unsigned primitive
+/// integer types.
+pub trait Integer: Number + Copy + Ord + sealed::Sealed {}
+
+/// Returns the number of ones in the binary representation of an integer.
+///
+/// Negative numbers are counted in their two's complement representation.
+pub fn count_ones<T: Integer>(n: T) -> u32 {
+  n.count_ones()
+}
+
+/// Returns the greatest common divisor of two integers.
+///
+/// The result is never negative. If either number is zero, the absolute value
+/// of the other number is returned.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+  let mut a = a.abs();
+  let mut b = b.abs();
+
+  while !b.is_zero() {
+    let rem = a % b;
+
+    a = b;
+    b = rem;
+  }
+
+  a
+}
+
+/// Returns `true` if an integer is a power of two.
+///
+/// Zero and negative numbers are never powers of two.
+pub fn is_power_of_two<T: Integer>(n: T) -> bool {
+  n > T::zero() && n.count_ones() == 1
+}
+
+/// Returns the least common multiple of two integers.
+///
+/// The result is never negative. If either number is zero, the result is zero.
+/// To avoid overflowing on intermediate values, one number is divided by the
+/// greatest common divisor before multiplying, so this function only
+/// overflows if the result itself does not fit in `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+  if a.is_zero() || b.is_zero() {
+    return T::zero();
+  }
+
+  a.abs() / gcd(a, b) * b.abs()
+}
+
+/// Returns the smallest power of two greater than or equal to an integer.
+///
+/// Zero and negative numbers return `1`.
+///
+/// # Panics
+///
+/// Panics if the result overflows `T`, such as `next_power_of_two(200u8)`.
+pub fn next_power_of_two<T: Integer>(n: T) -> T {
+  n.checked_next_power_of_two().expect("next power of two overflows")
+}
+
+/// Private module containing the sealing trait.
+mod sealed {
+  pub trait Sealed: Sized {
+    /// Returns the absolute value of the integer.
+    fn abs(self) -> Self;
+
+    /// Returns the number of ones in the binary representation of the integer.
+    fn count_ones(self) -> u32;
+
+    /// Returns the smallest power of two greater than or equal to the integer,
+    /// or `None` if it overflows.
+    fn checked_next_power_of_two(self) -> Option<Self>;
+  }
+}
+
+// Implement `Integer` for all primitive integer types.
+
+macro_rules! impl_signed {
+  ($($ty:ty => $unsigned:ty),*) => {
+    $(
+      impl Integer for $ty {}
+
+      impl sealed::Sealed for $ty {
+        fn abs(self) -> Self {
+          <$ty>::abs(self)
+        }
+
+        fn count_ones(self) -> u32 {
+          <$ty>::count_ones(self)
+        }
+
+        fn checked_next_power_of_two(self) -> Option<Self> {
+          match self < 1 {
+            true => Some(1),
+            false => <$ty>::try_from((self as $unsigned).checked_next_power_of_two()?).ok(),
+          }
+        }
+      }
+    )*
+  };
+}
+
+macro_rules! impl_unsigned {
+  ($($ty:ty),*) => {
+    $(
+      impl Integer for $ty {}
+
+      impl sealed::Sealed for $ty {
+        fn abs(self) -> Self {
+          self
+        }
+
+        fn count_ones(self) -> u32 {
+          <$ty>::count_ones(self)
+        }
+
+        fn checked_next_power_of_two(self) -> Option<Self> {
+          <$ty>::checked_next_power_of_two(self)
+        }
+      }
+    )*
+  };
+}
+
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_count_bits() {
+    assert_eq!(count_ones(0b1011u8), 3);
+    assert_eq!(count_ones(-1i16), 16);
+    assert!(is_power_of_two(64));
+    assert!(!is_power_of_two(0));
+    assert!(!is_power_of_two(-64));
+    assert!(!is_power_of_two(65u64));
+  }
+
+  #[test]
+  fn should_find_next_power_of_two() {
+    assert_eq!(next_power_of_two(17), 32);
+    assert_eq!(next_power_of_two(32u16), 32);
+    assert_eq!(next_power_of_two(0u8), 1);
+    assert_eq!(next_power_of_two(-5i8), 1);
+    assert_eq!(next_power_of_two(64i8), 64);
+  }
+
+  #[test]
+  #[should_panic]
+  fn next_power_of_two_should_panic_on_overflow() {
+    next_power_of_two(65i8);
+  }
+
+  #[test]
+  fn should_handle_coprime_numbers() {
+    assert_eq!(gcd(8u32, 15), 1);
+    assert_eq!(lcm(8u32, 15), 120);
+  }
+
+  #[test]
+  fn should_handle_common_factors() {
+    assert_eq!(gcd(12, 18), 6);
+    assert_eq!(lcm(12, 18), 36);
+    assert_eq!(gcd(-12i64, 18), 6);
+    assert_eq!(lcm(-12i64, 18), 36);
+  }
+
+  #[test]
+  fn should_handle_zero() {
+    assert_eq!(gcd(0, 7), 7);
+    assert_eq!(gcd(7, 0), 7);
+    assert_eq!(gcd(0, 0), 0);
+    assert_eq!(lcm(0, 7), 0);
+  }
+
+  #[test]
+  fn lcm_should_not_overflow_on_intermediate_values() {
+    assert_eq!(lcm(200u8, 100), 200);
+  }
+}
@@ -0,0 +1,124 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+/// An accumulator of summary statistics for a stream of values.
+///
+/// Values are not stored. The mean and variance are updated with Welford's
+/// online algorithm, which stays numerically stable over many values.
+///
+/// If no values have been pushed, every statistic except [`count()`] is NaN.
+///
+/// [`count()`]: Self::count
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+  count: u64,
+  mean: f64,
+  m2: f64,
+  min: f64,
+  max: f64,
+}
+
+impl Stats {
+  /// Creates a new, empty accumulator.
+  pub const fn new() -> Self {
+    Self { count: 0, mean: 0.0, m2: 0.0, min: f64::NAN, max: f64::NAN }
+  }
+
+  /// Returns the number of values pushed.
+  pub fn count(&self) -> u64 {
+    self.count
+  }
+
+  /// Returns the greatest value pushed.
+  pub fn max(&self) -> f64 {
+    self.max
+  }
+
+  /// Returns the arithmetic mean of the values pushed.
+  pub fn mean(&self) -> f64 {
+    match self.count {
+      0 => f64::NAN,
+      _ => self.mean,
+    }
+  }
+
+  /// Returns the least value pushed.
+  pub fn min(&self) -> f64 {
+    self.min
+  }
+
+  /// Adds a value to the accumulator.
+  pub fn push(&mut self, value: f64) {
+    self.count += 1;
+
+    let delta = value - self.mean;
+
+    self.mean += delta / self.count as f64;
+    self.m2 += delta * (value - self.mean);
+    self.min = self.min.min(value);
+    self.max = self.max.max(value);
+  }
+
+  /// Returns the population standard deviation of the values pushed.
+  pub fn std_dev(&self) -> f64 {
+    self.variance().sqrt()
+  }
+
+  /// Returns the population variance of the values pushed.
+  pub fn variance(&self) -> f64 {
+    match self.count {
+      0 => f64::NAN,
+      n => self.m2 / n as f64,
+    }
+  }
+}
+
+impl Default for Stats {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Extend<f64> for Stats {
+  fn extend<I: IntoIterator<Item = f64>>(&mut self, values: I) {
+    for value in values {
+      self.push(value);
+    }
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_compute_stats() {
+    let mut stats = Stats::new();
+
+    stats.extend([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+
+    assert_eq!(stats.count(), 8);
+    assert_eq!(stats.mean(), 5.0);
+    assert_eq!(stats.variance(), 4.0);
+    assert_eq!(stats.std_dev(), 2.0);
+    assert_eq!(stats.min(), 2.0);
+    assert_eq!(stats.max(), 9.0);
+  }
+
+  #[test]
+  fn should_return_nan_when_empty() {
+    let stats = Stats::default();
+
+    assert_eq!(stats.count(), 0);
+    assert!(stats.mean().is_nan());
+    assert!(stats.variance().is_nan());
+    assert!(stats.std_dev().is_nan());
+    assert!(stats.min().is_nan());
+    assert!(stats.max().is_nan());
+  }
+}
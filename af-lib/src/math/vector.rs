@@ -0,0 +1,145 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::prelude::*;
+
+/// A two-dimensional vector.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec2 {
+  pub x: f64,
+  pub y: f64,
+}
+
+/// A three-dimensional vector.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec3 {
+  pub x: f64,
+  pub y: f64,
+  pub z: f64,
+}
+
+/// Implements vector operations for a type with the given fields.
+macro_rules! impl_vector {
+  ($ty:ident { $($field:ident),+ }) => {
+    impl $ty {
+      /// Creates a new vector from its components.
+      pub const fn new($($field: f64),+) -> Self {
+        Self { $($field),+ }
+      }
+
+      /// Returns the dot product of this vector and another.
+      pub fn dot(self, other: Self) -> f64 {
+        0.0 $(+ self.$field * other.$field)+
+      }
+
+      /// Returns the length (magnitude) of the vector.
+      pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+      }
+
+      /// Linearly interpolates between this vector and another.
+      ///
+      /// A `t` of `0.0` returns this vector and a `t` of `1.0` returns `other`.
+      pub fn lerp(self, other: Self, t: f64) -> Self {
+        Self { $($field: super::lerp(self.$field, other.$field, t)),+ }
+      }
+
+      /// Returns a vector with the same direction and a length of `1.0`.
+      ///
+      /// If the vector has zero length, this function returns a zero vector.
+      pub fn normalized(self) -> Self {
+        match self.length() {
+          len if len == 0.0 => self,
+          len => self.scale(1.0 / len),
+        }
+      }
+
+      /// Returns the vector multiplied by a scalar.
+      pub fn scale(self, factor: f64) -> Self {
+        Self { $($field: self.$field * factor),+ }
+      }
+    }
+
+    impl Add for $ty {
+      type Output = Self;
+
+      fn add(self, rhs: Self) -> Self::Output {
+        Self { $($field: self.$field + rhs.$field),+ }
+      }
+    }
+
+    impl AddAssign for $ty {
+      fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+      }
+    }
+
+    impl Mul<f64> for $ty {
+      type Output = Self;
+
+      fn mul(self, rhs: f64) -> Self::Output {
+        self.scale(rhs)
+      }
+    }
+
+    impl Neg for $ty {
+      type Output = Self;
+
+      fn neg(self) -> Self::Output {
+        self.scale(-1.0)
+      }
+    }
+
+    impl Sub for $ty {
+      type Output = Self;
+
+      fn sub(self, rhs: Self) -> Self::Output {
+        Self { $($field: self.$field - rhs.$field),+ }
+      }
+    }
+
+    impl SubAssign for $ty {
+      fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+      }
+    }
+  };
+}
+
+impl_vector!(Vec2 { x, y });
+impl_vector!(Vec3 { x, y, z });
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_compute_length() {
+    assert_eq!(Vec2::new(3.0, 4.0).length(), 5.0);
+    assert_eq!(Vec3::new(3.0, 0.0, 4.0).length(), 5.0);
+  }
+
+  #[test]
+  fn should_normalize() {
+    assert!((Vec2::new(3.0, 4.0).normalized().length() - 1.0).abs() < 1e-12);
+    assert!((Vec3::new(1.0, -2.0, 7.5).normalized().length() - 1.0).abs() < 1e-12);
+    assert_eq!(Vec3::default().normalized(), Vec3::default());
+  }
+
+  #[test]
+  fn should_do_arithmetic() {
+    let a = Vec2::new(1.0, 2.0);
+    let b = Vec2::new(3.0, 5.0);
+
+    assert_eq!(a + b, Vec2::new(4.0, 7.0));
+    assert_eq!(b - a, Vec2::new(2.0, 3.0));
+    assert_eq!(a * 2.0, Vec2::new(2.0, 4.0));
+    assert_eq!(a.dot(b), 13.0);
+    assert_eq!(a.lerp(b, 0.5), Vec2::new(2.0, 3.5));
+  }
+}
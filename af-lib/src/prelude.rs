@@ -59,8 +59,8 @@ pub mod __af_lib_macro_helpers {
   pub use crate::main as __main;
   pub use crate::util::fmt::indent as __fmt_indent;
   pub use crate::util::log::{
-    flush as __flush_log, init as __log_init, set_level_of as __log_set_level_of,
-    Level as __log_level,
+    enter_span as __log_enter_span, flush as __flush_log, init as __log_init,
+    set_level_of as __log_set_level_of, Level as __log_level,
   };
   pub use crate::util::process::set_exit_code as __set_exit_code;
 }
@@ -6,16 +6,24 @@
 
 //! Time-related types and utilities.
 
+mod atomic_duration;
 mod date;
 mod date_time;
 pub mod duration;
 mod instant;
+mod rate_limiter;
+mod stopwatch;
+mod ticker;
 pub mod time_zone;
 pub mod timeout;
 
+pub use self::atomic_duration::AtomicDuration;
 pub use self::date::Date;
-pub use self::date_time::DateTime;
-pub use self::duration::Duration;
+pub use self::date_time::{CalendarSpan, DateTime};
+pub use self::duration::{avg_duration, max_duration, min_duration, Duration};
 pub use self::instant::Instant;
+pub use self::rate_limiter::RateLimiter;
+pub use self::stopwatch::Stopwatch;
+pub use self::ticker::Ticker;
 pub use self::time_zone::TimeZone;
 pub use self::timeout::timeout;
@@ -0,0 +1,140 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::Duration;
+use crate::prelude::*;
+
+/// A [`Duration`] that can be shared between threads and updated atomically.
+///
+/// The duration is stored as the bits of its number of seconds. Because
+/// durations are never negative, their bits order the same way as their
+/// values, so [`fetch_max()`][Self::fetch_max] is a single atomic operation.
+#[derive(Default)]
+pub struct AtomicDuration(AtomicU64);
+
+impl AtomicDuration {
+  /// Creates a new atomic duration.
+  pub fn new(value: Duration) -> Self {
+    Self(AtomicU64::new(to_bits(value)))
+  }
+
+  /// Adds to the duration, returning the previous value.
+  pub fn fetch_add(&self, value: Duration, order: Ordering) -> Duration {
+    let previous = self
+      .0
+      .fetch_update(order, Ordering::Relaxed, |bits| Some(to_bits(from_bits(bits) + value)))
+      .unwrap();
+
+    from_bits(previous)
+  }
+
+  /// Sets the duration to the maximum of its current value and `value`,
+  /// returning the previous value.
+  pub fn fetch_max(&self, value: Duration, order: Ordering) -> Duration {
+    from_bits(self.0.fetch_max(to_bits(value), order))
+  }
+
+  /// Consumes the atomic duration and returns the contained value.
+  pub fn into_inner(self) -> Duration {
+    from_bits(self.0.into_inner())
+  }
+
+  /// Loads the duration.
+  pub fn load(&self, order: Ordering) -> Duration {
+    from_bits(self.0.load(order))
+  }
+
+  /// Stores a new duration.
+  pub fn store(&self, value: Duration, order: Ordering) {
+    self.0.store(to_bits(value), order)
+  }
+
+  /// Stores a new duration, returning the previous value.
+  pub fn swap(&self, value: Duration, order: Ordering) -> Duration {
+    from_bits(self.0.swap(to_bits(value), order))
+  }
+}
+
+impl Debug for AtomicDuration {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    Debug::fmt(&self.load(Ordering::Relaxed), f)
+  }
+}
+
+impl From<Duration> for AtomicDuration {
+  fn from(value: Duration) -> Self {
+    Self::new(value)
+  }
+}
+
+/// Converts stored bits to a duration.
+fn from_bits(bits: u64) -> Duration {
+  Duration::seconds(f64::from_bits(bits))
+}
+
+/// Converts a duration to bits for storage.
+fn to_bits(value: Duration) -> u64 {
+  // Adding zero normalizes negative zero, whose bits would compare greater than
+  // every other duration.
+  (value.as_secs() + 0.0).to_bits()
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::Ordering::Relaxed;
+  use std::thread;
+
+  use super::*;
+
+  #[test]
+  fn should_add() {
+    let value = AtomicDuration::new(Duration::seconds(1));
+
+    assert_eq!(value.fetch_add(Duration::seconds(2), Relaxed), Duration::seconds(1));
+    assert_eq!(value.load(Relaxed), Duration::seconds(3));
+  }
+
+  #[test]
+  fn should_keep_max_of_concurrent_stores() {
+    let value = Arc::new(AtomicDuration::default());
+
+    let threads: Vec<_> = (0..8)
+      .map(|i| {
+        let value = value.clone();
+
+        thread::spawn(move || {
+          for ms in 0..1000 {
+            value.fetch_max(Duration::milliseconds(ms * 8 + i), Relaxed);
+          }
+        })
+      })
+      .collect();
+
+    for thread in threads {
+      thread.join().unwrap();
+    }
+
+    assert_eq!(value.load(Relaxed), Duration::milliseconds(999 * 8 + 7));
+  }
+
+  #[test]
+  fn should_never_be_negative() {
+    let value = AtomicDuration::new(Duration::seconds(-5));
+
+    assert_eq!(value.load(Relaxed), Duration::ZERO);
+
+    value.store(Duration::seconds(-0.0), Relaxed);
+    value.fetch_max(Duration::milliseconds(1), Relaxed);
+
+    assert_eq!(value.load(Relaxed), Duration::milliseconds(1));
+    assert_eq!(value.fetch_add(Duration::seconds(-1), Relaxed), Duration::milliseconds(1));
+    assert_eq!(value.load(Relaxed), Duration::milliseconds(1));
+  }
+}
@@ -16,7 +16,9 @@ pub struct Date(chrono::NaiveDate);
 impl Date {
   /// Creates a date from a given year, month, and day number.
   pub fn from_ymd(year: isize, month: usize, day: usize) -> Self {
-    Self(chrono::NaiveDate::from_ymd(year as i32, month as u32, day as u32))
+    let date = chrono::NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32);
+
+    Self(date.expect("invalid date"))
   }
 
   /// Returns the day of the month starting from `1`.
@@ -36,12 +38,12 @@ impl Date {
 
   /// Returns the next day.
   pub fn next(&self) -> Self {
-    Self(self.0.succ())
+    Self(self.0.succ_opt().expect("date out of range"))
   }
 
   /// Returns the previous day.
   pub fn prev(&self) -> Self {
-    Self(self.0.pred())
+    Self(self.0.pred_opt().expect("date out of range"))
   }
 
   /// Converts the date to a [`DateTime`] representing midnight in the local
@@ -53,7 +55,9 @@ impl Date {
   /// Converts the date to a [`DateTime`] representing midnight at the start of
   /// the same day.
   pub fn to_time(&self, zone: TimeZone) -> DateTime {
-    zone.as_tz().from_local_date(&self.0).and_hms_opt(0, 0, 0).unwrap().into()
+    let midnight = self.0.and_hms_opt(0, 0, 0).unwrap();
+
+    zone.as_tz().from_local_datetime(&midnight).earliest().unwrap().into()
   }
 
   /// Converts the date to a [`DateTime`] representing midnight in UTC at the
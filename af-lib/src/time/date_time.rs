@@ -6,7 +6,7 @@
 
 //! Contains functionality associated with [`DateTime`].
 
-use chrono::{TimeZone as _, Timelike};
+use chrono::{Datelike, TimeZone as _, Timelike};
 use chrono_tz::Tz;
 
 use super::{Date, Duration, TimeZone};
@@ -16,15 +16,35 @@ use crate::prelude::*;
 #[derive(Clone, Copy, From)]
 pub struct DateTime(chrono::DateTime<Tz>);
 
+/// A span of time expressed as calendar components.
+///
+/// Returned from [`DateTime::calendar_diff()`]. Each component is within its
+/// natural range, for example `months` is always less than `12`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CalendarSpan {
+  /// The number of whole years.
+  pub years: usize,
+  /// The number of whole months from `0` to `11`.
+  pub months: usize,
+  /// The number of whole days.
+  pub days: usize,
+  /// The number of whole hours from `0` to `23`.
+  pub hours: usize,
+  /// The number of whole minutes from `0` to `59`.
+  pub minutes: usize,
+  /// The number of whole seconds from `0` to `59`.
+  pub seconds: usize,
+}
+
 impl DateTime {
   /// Returns a value representing the maximum local date and time.
   pub fn max_value() -> DateTime {
-    DateTime(chrono::MAX_DATETIME.with_timezone(TimeZone::local().as_tz()))
+    DateTime(chrono::DateTime::<chrono::Utc>::MAX_UTC.with_timezone(TimeZone::local().as_tz()))
   }
 
   /// Returns a value representing the minimum local date and time.
   pub fn min_value() -> DateTime {
-    DateTime(chrono::MIN_DATETIME.with_timezone(TimeZone::local().as_tz()))
+    DateTime(chrono::DateTime::<chrono::Utc>::MIN_UTC.with_timezone(TimeZone::local().as_tz()))
   }
 
   /// Returns a value representing the current local date and time.
@@ -34,7 +54,7 @@ impl DateTime {
 
   /// Returns a date and time representing a Unix timestamp in milliseconds.
   pub fn from_unix_ms(timestamp: i64) -> Self {
-    Self(TimeZone::local().as_tz().timestamp_millis(timestamp))
+    Self(TimeZone::local().as_tz().timestamp_millis_opt(timestamp).unwrap())
   }
 
   /// Formats the date and time according to RFC 3339.
@@ -45,9 +65,44 @@ impl DateTime {
     }
   }
 
+  /// Returns the difference between this date and time and another as
+  /// calendar components.
+  ///
+  /// The components are computed on the calendar of this date and time's time
+  /// zone, so a month is the time between the same day of consecutive months.
+  /// The span is never negative, regardless of which date and time is earlier.
+  pub fn calendar_diff(&self, other: DateTime) -> CalendarSpan {
+    let other = other.0.with_timezone(&self.0.timezone());
+
+    let (start, end) = match other < self.0 {
+      true => (other.naive_local(), self.0.naive_local()),
+      false => (self.0.naive_local(), other.naive_local()),
+    };
+
+    // Count whole months, then break the remainder into smaller units.
+
+    let mut months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32;
+    let add_months = |months: i32| start.checked_add_months(chrono::Months::new(months as u32));
+
+    while months > 0 && add_months(months).is_none_or(|shifted| shifted > end) {
+      months -= 1;
+    }
+
+    let remainder = (end - add_months(months).unwrap_or(start)).num_seconds() as usize;
+
+    CalendarSpan {
+      years: months as usize / 12,
+      months: months as usize % 12,
+      days: remainder / 86400,
+      hours: remainder / 3600 % 24,
+      minutes: remainder / 60 % 60,
+      seconds: remainder % 60,
+    }
+  }
+
   /// Returns the date component.
   pub fn date(&self) -> Date {
-    self.0.date().naive_local().into()
+    self.0.date_naive().into()
   }
 
   /// Returns the duration since this date and time occurred.
@@ -109,7 +164,9 @@ impl DateTime {
   /// Returns a new date and time representing midnight at the beginning of the
   /// same day.
   pub fn start_of_day(&self) -> DateTime {
-    Self(self.0.date().and_hms(0, 0, 0))
+    let midnight = self.0.date_naive().and_hms_opt(0, 0, 0).unwrap();
+
+    Self(self.0.timezone().from_local_datetime(&midnight).earliest().unwrap())
   }
 
   /// Converts the date and time to the local time zone.
@@ -150,6 +207,17 @@ impl Debug for DateTime {
   }
 }
 
+impl<'de> Deserialize<'de> for DateTime {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let value = String::deserialize(deserializer)?;
+
+    value.parse().map_err(serde::de::Error::custom)
+  }
+}
+
 impl Display for DateTime {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     self.as_rfc3339().fmt(f)
@@ -158,6 +226,18 @@ impl Display for DateTime {
 
 impl Eq for DateTime {}
 
+impl FromStr for DateTime {
+  type Err = chrono::ParseError;
+
+  /// Parses a date and time in RFC 3339 format, such as
+  /// `2020-09-13T12:26:40Z` or `2020-09-13T14:26:40+02:00`.
+  ///
+  /// The result is in UTC.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Ok(Self(chrono::DateTime::parse_from_rfc3339(s)?.with_timezone(&Tz::UTC)))
+  }
+}
+
 impl Ord for DateTime {
   fn cmp(&self, other: &Self) -> cmp::Ordering {
     self.0.cmp(&other.0)
@@ -166,7 +246,16 @@ impl Ord for DateTime {
 
 impl PartialOrd for DateTime {
   fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-    self.0.partial_cmp(&other.0)
+    Some(self.cmp(other))
+  }
+}
+
+impl Serialize for DateTime {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_str(&self.as_rfc3339())
   }
 }
 
@@ -187,3 +276,42 @@ impl Sub<DateTime> for DateTime {
     (self.0 - rhs.0).into()
   }
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use serde::de::IntoDeserializer;
+
+  use super::*;
+
+  #[test]
+  fn should_round_trip_rfc3339() {
+    let time = DateTime::from_unix_ms(1_600_000_123);
+
+    assert_eq!(time.to_string().parse::<DateTime>().unwrap(), time);
+    assert_eq!(time.to_utc().to_string(), "1970-01-19T12:26:40.123Z");
+
+    let deserialize = |s: &'static str| {
+      DateTime::deserialize(IntoDeserializer::<serde::de::value::Error>::into_deserializer(s))
+    };
+
+    assert_eq!(deserialize("1970-01-19T12:26:40.123Z").unwrap(), time);
+    assert_eq!(deserialize("1970-01-19T14:26:40.123+02:00").unwrap(), time);
+    assert!(deserialize("yesterday").is_err());
+  }
+
+  #[test]
+  fn calendar_diff_should_break_down_components() {
+    let start = Date::from_ymd(2020, 1, 31).to_utc_time() + Duration::minutes(600);
+    let end =
+      Date::from_ymd(2021, 4, 2).to_utc_time() + Duration::minutes(750) + Duration::seconds(15);
+
+    let expected =
+      CalendarSpan { years: 1, months: 2, days: 2, hours: 2, minutes: 30, seconds: 15 };
+
+    assert_eq!(end.calendar_diff(start), expected);
+    assert_eq!(start.calendar_diff(end), expected);
+    assert_eq!(start.calendar_diff(start), CalendarSpan::default());
+  }
+}
@@ -20,7 +20,42 @@ pub struct Duration {
   secs: f64,
 }
 
+/// Returns the average of the given durations.
+///
+/// If there are no durations, this function returns [`Duration::ZERO`].
+pub fn avg_duration(durations: impl IntoIterator<Item = Duration>) -> Duration {
+  let mut count = 0usize;
+  let mut total = Duration::ZERO;
+
+  for duration in durations {
+    count += 1;
+    total += duration;
+  }
+
+  match count {
+    0 => Duration::ZERO,
+    n => total / n,
+  }
+}
+
+/// Returns the longest of the given durations.
+///
+/// If there are no durations, this function returns [`Duration::ZERO`].
+pub fn max_duration(durations: impl IntoIterator<Item = Duration>) -> Duration {
+  durations.into_iter().max().unwrap_or(Duration::ZERO)
+}
+
+/// Returns the shortest of the given durations.
+///
+/// If there are no durations, this function returns [`Duration::ZERO`].
+pub fn min_duration(durations: impl IntoIterator<Item = Duration>) -> Duration {
+  durations.into_iter().min().unwrap_or(Duration::ZERO)
+}
+
 impl Duration {
+  /// A duration of zero.
+  pub const ZERO: Duration = Duration { secs: 0.0 };
+
   /// Return the duration as a number of weeks.
   pub fn as_weeks(self) -> f64 {
     self.as_secs() / 7.0 / 24.0 / 60.0 / 60.0
@@ -75,6 +110,54 @@ impl Duration {
     Duration { secs: f64::INFINITY }
   }
 
+  /// Returns a representation of the duration for display in up to two units,
+  /// such as `1h 5m`, `3d 4h`, or `500ms`.
+  ///
+  /// The largest unit from days to milliseconds is shown along with the next
+  /// smaller unit, which is rounded and omitted if zero. An infinite duration
+  /// is displayed as `forever`.
+  pub fn humanize(&self) -> String {
+    /// Units in milliseconds from largest to smallest.
+    const UNITS: &[(u64, &str)] =
+      &[(86_400_000, "d"), (3_600_000, "h"), (60_000, "m"), (1_000, "s"), (1, "ms")];
+
+    if self.is_infinite() {
+      return "forever".into();
+    }
+
+    let largest =
+      |ms: u64| UNITS.iter().position(|(unit, _)| ms >= *unit).unwrap_or(UNITS.len() - 1);
+
+    // Round to the smaller unit, repeating if that carries into a larger unit.
+
+    let mut ms = self.as_ms().round() as u64;
+    let mut major = largest(ms);
+
+    loop {
+      let step = UNITS[cmp::min(major + 1, UNITS.len() - 1)].0;
+
+      ms = (ms as f64 / step as f64).round() as u64 * step;
+
+      match largest(ms) {
+        i if i == major => break,
+        i => major = i,
+      }
+    }
+
+    let minor = cmp::min(major + 1, UNITS.len() - 1);
+    let (major_unit, major_name) = UNITS[major];
+    let (minor_unit, minor_name) = UNITS[minor];
+
+    let mut output = format!("{}{}", ms / major_unit, major_name);
+    let remainder = ms % major_unit / minor_unit;
+
+    if minor != major && remainder > 0 {
+      write!(output, " {}{}", remainder, minor_name).unwrap();
+    }
+
+    output
+  }
+
   /// Returns a [`Duration`] representing a number of Hz.
   pub fn hz(hz: impl AsPrimitive<f64>) -> Duration {
     Self::seconds(1.0 / hz.as_())
@@ -119,6 +202,15 @@ impl Duration {
     Duration { secs: secs.max(0.0) }
   }
 
+  /// Returns a compact representation of the duration for display.
+  ///
+  /// The duration is displayed in the same units as the [`Display`]
+  /// implementation, but with a short suffix and no space, such as `250ms`,
+  /// `2.5s`, or `3d`. An infinite duration is displayed as `∞`.
+  pub fn short(&self) -> impl Display {
+    Short(*self)
+  }
+
   /// Converts this duration to a `std::time::Duration`.
   pub fn to_std(self) -> std::time::Duration {
     /// The maximum f64 value with whole number precision.
@@ -157,22 +249,119 @@ impl Debug for Duration {
 impl Display for Duration {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     if self.secs.is_infinite() {
-      write!(f, "forever")
-    } else if self.secs < 2.0 {
-      write!(f, "{} ms", self.as_ms().round_to_places(3))
-    } else if self.secs < 120.0 {
-      write!(f, "{} secs", self.as_secs().round_to_places(3))
-    } else if self.secs < 7_200.0 {
-      write!(f, "{} mins", self.as_mins().round_to_places(2))
-    } else if self.secs < 172_800.0 {
-      write!(f, "{} hours", self.as_hours().round_to_places(2))
-    } else if self.secs < 604_800.0 {
-      write!(f, "{} days", self.as_days().round_to_places(2))
-    } else if self.secs < 31_557_600.0 {
-      write!(f, "{} weeks", self.as_weeks().round_to_places(1))
-    } else {
-      write!(f, "{} years", (self.secs / 31_557_600.0).round_to_places(1))
+      return write!(f, "forever");
     }
+
+    let unit = DisplayUnit::of(*self);
+
+    write!(f, "{} {}", unit.rounded(*self), unit.name)
+  }
+}
+
+/// A unit that finite durations are displayed in.
+pub(crate) struct DisplayUnit {
+  /// The length of the unit in seconds.
+  pub secs: f64,
+  /// The length in seconds of the shortest duration displayed in a larger
+  /// unit.
+  pub until: f64,
+  /// The number of decimal places displayed.
+  places: usize,
+  /// The long name of the unit.
+  name: &'static str,
+  /// The short name of the unit.
+  short_name: &'static str,
+  /// The singular and plural names of the unit in prose.
+  pub words: (&'static str, &'static str),
+}
+
+/// The units that finite durations are displayed in, from smallest to largest.
+const DISPLAY_UNITS: [DisplayUnit; 7] = [
+  DisplayUnit {
+    secs: 0.001,
+    until: 2.0,
+    places: 3,
+    name: "ms",
+    short_name: "ms",
+    words: ("millisecond", "milliseconds"),
+  },
+  DisplayUnit {
+    secs: 1.0,
+    until: 120.0,
+    places: 3,
+    name: "secs",
+    short_name: "s",
+    words: ("second", "seconds"),
+  },
+  DisplayUnit {
+    secs: 60.0,
+    until: 7_200.0,
+    places: 2,
+    name: "mins",
+    short_name: "m",
+    words: ("minute", "minutes"),
+  },
+  DisplayUnit {
+    secs: 3_600.0,
+    until: 172_800.0,
+    places: 2,
+    name: "hours",
+    short_name: "h",
+    words: ("hour", "hours"),
+  },
+  DisplayUnit {
+    secs: 86_400.0,
+    until: 604_800.0,
+    places: 2,
+    name: "days",
+    short_name: "d",
+    words: ("day", "days"),
+  },
+  DisplayUnit {
+    secs: 604_800.0,
+    until: 31_557_600.0,
+    places: 1,
+    name: "weeks",
+    short_name: "w",
+    words: ("week", "weeks"),
+  },
+  DisplayUnit {
+    secs: 31_557_600.0,
+    until: f64::INFINITY,
+    places: 1,
+    name: "years",
+    short_name: "y",
+    words: ("year", "years"),
+  },
+];
+
+impl DisplayUnit {
+  /// Returns the unit to display a finite duration in.
+  pub fn of(duration: Duration) -> &'static Self {
+    let last = &DISPLAY_UNITS[DISPLAY_UNITS.len() - 1];
+
+    DISPLAY_UNITS.iter().find(|unit| duration.secs < unit.until).unwrap_or(last)
+  }
+
+  /// Returns a duration in this unit, rounded for display.
+  fn rounded(&self, duration: Duration) -> f64 {
+    (duration.secs / self.secs).round_to_places(self.places)
+  }
+}
+
+/// A compact representation of a [`Duration`] returned from
+/// [`Duration::short()`].
+struct Short(Duration);
+
+impl Display for Short {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.0.secs.is_infinite() {
+      return write!(f, "∞");
+    }
+
+    let unit = DisplayUnit::of(self.0);
+
+    write!(f, "{}{}", unit.rounded(self.0), unit.short_name)
   }
 }
 
@@ -224,11 +413,53 @@ impl From<Duration> for chrono::Duration {
   }
 }
 
+impl<'de> Deserialize<'de> for Duration {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    /// A visitor that accepts a string or a number of seconds.
+    struct Visitor;
+
+    impl<'de> serde::de::Visitor<'de> for Visitor {
+      type Value = Duration;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a duration string or a number of seconds")
+      }
+
+      fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Duration, E> {
+        Ok(Duration::seconds(value))
+      }
+
+      fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Duration, E> {
+        Ok(Duration::seconds(value))
+      }
+
+      fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Duration, E> {
+        Ok(Duration::seconds(value))
+      }
+
+      fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Duration, E> {
+        value.parse().map_err(E::custom)
+      }
+    }
+
+    deserializer.deserialize_any(Visitor)
+  }
+}
+
 impl FromStr for Duration {
   type Err = ParseError;
 
+  /// Parses a human-readable duration such as `30s` or `2.5 hours`.
+  ///
+  /// The string `forever` parses as an infinite duration.
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    parse(s).map(From::from)
+    match s.trim() {
+      "forever" => Ok(Duration::forever()),
+      s => parse(s).map(From::from),
+    }
   }
 }
 
@@ -260,6 +491,23 @@ impl Ord for Duration {
   }
 }
 
+impl Serialize for Duration {
+  /// Serializes the duration as a number of seconds, or as the string
+  /// `forever` if it is infinite.
+  ///
+  /// The [`Display`][fmt::Display] string is rounded for readability, so it is
+  /// not used here.
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    match self.is_infinite() {
+      true => serializer.serialize_str("forever"),
+      false => serializer.serialize_f64(self.secs),
+    }
+  }
+}
+
 impl Sub<Self> for Duration {
   type Output = Self;
 
@@ -274,3 +522,103 @@ impl SubAssign<Self> for Duration {
     self.secs = f64::max(self.secs - rhs.secs, 0.0);
   }
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_aggregate() {
+    let durations = [Duration::seconds(1), Duration::seconds(3), Duration::seconds(2)];
+
+    assert_eq!(avg_duration(durations.iter().copied()), Duration::seconds(2));
+    assert_eq!(max_duration(durations.iter().copied()), Duration::seconds(3));
+    assert_eq!(min_duration(durations.iter().copied()), Duration::seconds(1));
+  }
+
+  #[test]
+  fn should_aggregate_empty_input_to_zero() {
+    assert_eq!(avg_duration(None), Duration::ZERO);
+    assert_eq!(max_duration(None), Duration::ZERO);
+    assert_eq!(min_duration(None), Duration::ZERO);
+  }
+
+  #[test]
+  fn should_deserialize_strings_and_numbers() {
+    use serde::de::value::Error;
+    use serde::de::IntoDeserializer;
+
+    let from_str = |s: &str| Duration::deserialize(IntoDeserializer::<Error>::into_deserializer(s));
+
+    for duration in [Duration::seconds(30), Duration::minutes(5), Duration::forever()] {
+      assert_eq!(from_str(&duration.to_string()).unwrap(), duration);
+    }
+
+    assert_eq!(from_str("30s").unwrap(), Duration::seconds(30));
+    assert!(from_str("soon").is_err());
+
+    assert_eq!(
+      Duration::deserialize(IntoDeserializer::<Error>::into_deserializer(1.5)).unwrap(),
+      Duration::seconds(1.5)
+    );
+
+    assert_eq!(
+      Duration::deserialize(IntoDeserializer::<Error>::into_deserializer(90u32)).unwrap(),
+      Duration::seconds(90)
+    );
+  }
+
+  #[test]
+  fn should_round_trip_through_json() {
+    let durations = [
+      Duration::ZERO,
+      Duration::days(10),
+      Duration::seconds(864_000.123_456_789),
+      Duration::milliseconds(1.1),
+      Duration::forever(),
+    ];
+
+    for duration in durations {
+      let json = serde_json::to_string(&duration).unwrap();
+
+      assert_eq!(serde_json::from_str::<Duration>(&json).unwrap(), duration, "{}", json);
+    }
+
+    assert_eq!(serde_json::to_string(&Duration::days(10)).unwrap(), "864000.0");
+    assert_eq!(serde_json::to_string(&Duration::forever()).unwrap(), "\"forever\"");
+    assert_eq!(serde_json::from_str::<Duration>("\"30s\"").unwrap(), Duration::seconds(30));
+  }
+
+  #[test]
+  fn should_be_zero_below_one_nanosecond() {
+    assert!(Duration::ZERO.is_zero());
+    assert!(Duration::seconds(0.5e-9).is_zero());
+    assert!(!Duration::seconds(1e-9).is_zero());
+  }
+
+  #[test]
+  fn should_humanize() {
+    assert_eq!(Duration::ZERO.humanize(), "0ms");
+    assert_eq!(Duration::milliseconds(500).humanize(), "500ms");
+    assert_eq!(Duration::milliseconds(1500).humanize(), "1s 500ms");
+    assert_eq!(Duration::seconds(45).humanize(), "45s");
+    assert_eq!(Duration::seconds(3903).humanize(), "1h 5m");
+    assert_eq!(Duration::seconds(3600).humanize(), "1h");
+    assert_eq!(Duration::minutes(76 * 60).humanize(), "3d 4h");
+    assert_eq!(Duration::seconds(7199.9).humanize(), "2h");
+    assert_eq!(Duration::forever().humanize(), "forever");
+  }
+
+  #[test]
+  fn should_display_short_form() {
+    assert_eq!(Duration::milliseconds(250).short().to_string(), "250ms");
+    assert_eq!(Duration::seconds(2.5).short().to_string(), "2.5s");
+    assert_eq!(Duration::minutes(3).short().to_string(), "3m");
+    assert_eq!(Duration::minutes(180).short().to_string(), "3h");
+    assert_eq!(Duration::days(3).short().to_string(), "3d");
+    assert_eq!(Duration::weeks(2).short().to_string(), "2w");
+    assert_eq!(Duration::forever().short().to_string(), "∞");
+  }
+}
@@ -0,0 +1,96 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::Mutex;
+
+use super::{Duration, Instant};
+use crate::prelude::*;
+
+/// A cloneable token-bucket rate limiter.
+///
+/// The bucket holds up to `burst` tokens and refills at `rate_per_sec` tokens
+/// per second. Each call to [`acquire()`][Self::acquire] takes one token,
+/// waiting for it to refill if necessary.
+#[derive(Clone)]
+pub struct RateLimiter {
+  burst: f64,
+  rate_per_sec: f64,
+  state: Arc<Mutex<State>>,
+}
+
+/// The shared state of a rate limiter.
+struct State {
+  /// The number of available tokens, which is negative if tokens have been
+  /// reserved by waiting callers.
+  tokens: f64,
+  /// When `tokens` was last refilled.
+  refilled_at: Instant,
+}
+
+impl RateLimiter {
+  /// Creates a new rate limiter with a full bucket.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `rate_per_sec` is not positive or `burst` is zero.
+  pub fn new(rate_per_sec: f64, burst: u32) -> Self {
+    assert!(rate_per_sec > 0.0, "the rate must be positive");
+    assert!(burst > 0, "the burst size must be greater than zero");
+
+    let burst = burst as f64;
+
+    Self {
+      burst,
+      rate_per_sec,
+      state: Arc::new(Mutex::new(State { tokens: burst, refilled_at: Instant::now() })),
+    }
+  }
+
+  /// Waits until a token is available and takes it.
+  ///
+  /// Callers are served in the order they call this function. A token is
+  /// reserved immediately, so canceling the returned future does not return
+  /// the token to the bucket.
+  pub async fn acquire(&self) {
+    let wait = {
+      let mut state = self.state.lock().unwrap();
+      let refill = state.refilled_at.duration_since().as_secs() * self.rate_per_sec;
+
+      state.tokens = (state.tokens + refill).min(self.burst) - 1.0;
+      state.refilled_at = Instant::now();
+
+      Duration::seconds(-state.tokens / self.rate_per_sec)
+    };
+
+    if !wait.is_zero() {
+      wait.elapsed().await;
+    }
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[async_test]
+  async fn should_limit_rate() {
+    let limiter = RateLimiter::new(100.0, 2);
+    let start = Instant::now();
+
+    for _ in 0..12 {
+      limiter.clone().acquire().await;
+    }
+
+    // The first two are the burst, and the other ten take 10ms each.
+
+    let elapsed = start.duration_since();
+
+    assert!(elapsed >= Duration::milliseconds(95));
+    assert!(elapsed < Duration::milliseconds(150));
+  }
+}
@@ -0,0 +1,109 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::{Duration, Instant};
+use crate::prelude::*;
+
+/// Measures elapsed time, excluding time spent paused.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stopwatch {
+  accumulated: Duration,
+  running_since: Option<Instant>,
+}
+
+impl Stopwatch {
+  /// Returns the total time the stopwatch has been running.
+  pub fn elapsed(&self) -> Duration {
+    match self.running_since {
+      Some(since) => self.accumulated + since.duration_since(),
+      None => self.accumulated,
+    }
+  }
+
+  /// Returns `true` if the stopwatch is running.
+  pub fn is_running(&self) -> bool {
+    self.running_since.is_some()
+  }
+
+  /// Creates a new stopwatch that is paused with no elapsed time.
+  pub fn new() -> Self {
+    default()
+  }
+
+  /// Pauses the stopwatch.
+  ///
+  /// If the stopwatch is already paused, this function does nothing.
+  pub fn pause(&mut self) {
+    if let Some(since) = self.running_since.take() {
+      self.accumulated += since.duration_since();
+    }
+  }
+
+  /// Resets the elapsed time to zero without pausing or resuming the
+  /// stopwatch.
+  pub fn reset(&mut self) {
+    self.accumulated = Duration::ZERO;
+
+    if self.running_since.is_some() {
+      self.running_since = Some(Instant::now());
+    }
+  }
+
+  /// Resumes the stopwatch.
+  ///
+  /// If the stopwatch is already running, this function does nothing.
+  pub fn resume(&mut self) {
+    if self.running_since.is_none() {
+      self.running_since = Some(Instant::now());
+    }
+  }
+
+  /// Creates a new stopwatch that is running.
+  pub fn start() -> Self {
+    Self { accumulated: Duration::ZERO, running_since: Some(Instant::now()) }
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[async_test]
+  async fn should_exclude_paused_time() {
+    let mut stopwatch = Stopwatch::start();
+
+    Duration::milliseconds(20).elapsed().await;
+    stopwatch.pause();
+
+    let paused = stopwatch.elapsed();
+
+    Duration::milliseconds(50).elapsed().await;
+
+    assert_eq!(stopwatch.elapsed(), paused);
+
+    stopwatch.resume();
+    Duration::milliseconds(20).elapsed().await;
+
+    let elapsed = stopwatch.elapsed();
+
+    assert!(elapsed >= Duration::milliseconds(40));
+    assert!(elapsed < Duration::milliseconds(80));
+  }
+
+  #[test]
+  fn should_reset() {
+    let mut stopwatch = Stopwatch::new();
+
+    stopwatch.resume();
+    stopwatch.pause();
+    stopwatch.reset();
+
+    assert!(!stopwatch.is_running());
+    assert_eq!(stopwatch.elapsed(), Duration::ZERO);
+  }
+}
@@ -0,0 +1,112 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::{Duration, Instant};
+use crate::concurrency::future;
+use crate::prelude::*;
+
+/// Waits for ticks on a fixed schedule that does not drift.
+///
+/// Each tick is due a whole number of periods after the ticker was created. If
+/// the next tick is already due when [`tick()`][Self::tick] is called, it
+/// returns immediately, so a late wakeup or slow work between ticks does not
+/// skip a tick. If more than one tick is already due, all but the latest are
+/// skipped so that the ticker realigns to the schedule.
+#[derive(Debug)]
+pub struct Ticker {
+  period: Duration,
+  start: Instant,
+  ticks: u64,
+}
+
+impl Ticker {
+  /// Creates a new ticker whose first tick is due one `period` from now.
+  pub fn new(period: Duration) -> Self {
+    Self { period, start: Instant::now(), ticks: 0 }
+  }
+
+  /// Returns the period between ticks.
+  pub fn period(&self) -> Duration {
+    self.period
+  }
+
+  /// Waits for the next tick and returns the instant it was due.
+  ///
+  /// If `period` is infinite, this function never returns.
+  pub async fn tick(&mut self) -> Instant {
+    if self.period.is_infinite() {
+      future::never().await;
+    }
+
+    self.ticks = next_tick(self.ticks, self.start.duration_since(), self.period);
+
+    let next = self.start + self.period * self.ticks as f64;
+
+    next.occurred().await;
+    next
+  }
+}
+
+/// Returns the number of the next tick to wait for, given the number of the
+/// previous tick and the time elapsed since the ticker was created.
+///
+/// All ticks that are already due except the latest are skipped.
+fn next_tick(previous: u64, elapsed: Duration, period: Duration) -> u64 {
+  let latest_due = match period.is_zero() {
+    true => 0,
+    false => (elapsed.as_secs() / period.as_secs()).floor() as u64,
+  };
+
+  cmp::max(previous + 1, latest_due)
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_not_skip_late_ticks() {
+    let period = Duration::milliseconds(20);
+
+    assert_eq!(next_tick(0, Duration::milliseconds(5), period), 1);
+    assert_eq!(next_tick(1, Duration::milliseconds(30), period), 2);
+    assert_eq!(next_tick(1, Duration::milliseconds(42), period), 2);
+  }
+
+  #[test]
+  fn should_skip_missed_ticks() {
+    let period = Duration::milliseconds(20);
+
+    assert_eq!(next_tick(1, Duration::milliseconds(65), period), 3);
+    assert_eq!(next_tick(1, Duration::milliseconds(100), period), 5);
+    assert_eq!(next_tick(3, Duration::ZERO, Duration::ZERO), 4);
+  }
+
+  #[async_test]
+  async fn should_not_drift() {
+    let mut ticker = Ticker::new(Duration::milliseconds(20));
+    let start = Instant::now();
+    let mut previous = ticker.start;
+
+    for _ in 1..=5 {
+      let due = ticker.tick().await;
+
+      assert!(due > previous);
+      assert!(Instant::now() >= due);
+
+      previous = due;
+
+      Duration::milliseconds(15).elapsed().await;
+    }
+
+    // Without drift, five ticks take about 100ms plus the last 15ms of work.
+    // With drift, the work would add up to 75ms on top of the 100ms.
+
+    assert!(start.duration_since() < Duration::milliseconds(160));
+  }
+}
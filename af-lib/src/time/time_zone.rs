@@ -57,7 +57,7 @@ impl TimeZone {
         // Next, try running a command to find the current time zone.
 
         let output =
-          Command::new("timedatectl").args(&["show", "--property=Timezone", "--value"]).output();
+          Command::new("timedatectl").args(["show", "--property=Timezone", "--value"]).output();
 
         if let Ok(output) = output {
           if output.status.success() {
@@ -9,12 +9,18 @@
 pub mod defer;
 pub mod failure;
 pub mod fmt;
+mod global;
 pub mod iter;
+pub mod json;
 pub mod log;
 pub mod panic;
+mod pool;
 pub mod process;
 pub mod random;
 mod shared_str;
+mod shutdown;
+pub mod stream;
+pub mod string;
 mod uuid;
 
 pub use futures_lite::pin;
@@ -24,11 +30,15 @@ pub use pin_project::pin_project;
 pub use self::defer::defer;
 #[doc(inline)]
 pub use self::failure::{failure, Failure};
+pub(crate) use self::global::teardown;
+pub use self::global::{global, Global, GlobalRef};
 #[doc(inline)]
 pub use self::panic::Panic;
+pub use self::pool::{Pool, Pooled};
 #[doc(inline)]
 pub use self::random::{random, Random};
 pub use self::shared_str::SharedStr;
+pub use self::shutdown::{Shutdown, Subscription};
 pub use self::uuid::Uuid;
 pub use once_cell::sync::Lazy;
 
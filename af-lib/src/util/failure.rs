@@ -6,7 +6,7 @@
 
 //! A general purpose error type.
 
-pub use af_macros::{fail, failure};
+pub use af_macros::{fail, fail_unwrap as unwrap, fail_when as when, failure};
 
 use crate::math::AsPrimitive;
 use crate::prelude::*;
@@ -23,7 +23,7 @@ struct Cause {
   file: Cow<'static, str>,
   line: usize,
   message: SharedStr,
-  cause: Option<Arc<Cause>>,
+  source: Option<Arc<dyn Error + Send + Sync>>,
 }
 
 /// Represents either success (`Ok`) or failure (`Err`).
@@ -45,7 +45,27 @@ impl Failure {
         file: file.into(),
         line: line.as_(),
         message: message.into(),
-        cause: cause.map(|c| c.cause),
+        source: cause.map(|cause| Arc::new(cause) as Arc<dyn Error + Send + Sync>),
+      }),
+    }
+  }
+
+  /// Creates a new failure caused by another error.
+  ///
+  /// The error is returned from [`Error::source()`] so that the full chain of
+  /// errors can be inspected.
+  pub fn with_source(
+    file: impl Into<Cow<'static, str>>,
+    line: impl AsPrimitive<usize>,
+    message: impl Into<SharedStr>,
+    source: impl Error + Send + Sync + 'static,
+  ) -> Self {
+    Self {
+      cause: Arc::new(Cause {
+        file: file.into(),
+        line: line.as_(),
+        message: message.into(),
+        source: Some(Arc::new(source)),
       }),
     }
   }
@@ -64,22 +84,11 @@ impl Failure {
   pub fn message(&self) -> &SharedStr {
     &self.cause.message
   }
+}
 
-  /// Returns an iterator over all causes of the failure.
-  fn causes(&self) -> impl Iterator<Item = &Cause> {
-    struct Iter<'a>(Option<&'a Arc<Cause>>);
-
-    impl<'a> Iterator for Iter<'a> {
-      type Item = &'a Cause;
-
-      fn next(&mut self) -> Option<Self::Item> {
-        let item = self.0?;
-        self.0 = item.cause.as_ref();
-        Some(item)
-      }
-    }
-
-    Iter(Some(&self.cause))
+impl Error for Failure {
+  fn source(&self) -> Option<&(dyn Error + 'static)> {
+    Some(self.cause.source.as_deref()?)
   }
 }
 
@@ -110,12 +119,33 @@ impl Debug for Failure {
 
 impl Display for Failure {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    let mut causes = self.causes();
+    write!(f, "{}", self.cause)?;
+
+    // Write the source, which is also a failure or is indented like a message.
+    // With alternate formatting, also write the sources of other errors.
+
+    let mut source = match &self.cause.source {
+      Some(source) => match source.downcast_ref::<Failure>() {
+        Some(failure) => {
+          return match f.alternate() {
+            true => write!(f, "\n{:#}", failure),
+            false => write!(f, "\n{}", failure),
+          };
+        }
 
-    write!(f, "{}", causes.next().unwrap())?;
+        None => Some(&**source as &(dyn Error + 'static)),
+      },
 
-    for cause in causes {
-      write!(f, "\n{}", cause)?;
+      None => None,
+    };
+
+    while let Some(err) = source {
+      write!(f, "\n{}", fmt::indent("  ", "  ", err))?;
+
+      source = match f.alternate() {
+        true => err.source(),
+        false => None,
+      };
     }
 
     Ok(())
@@ -127,3 +157,91 @@ impl Display for Cause {
     write!(f, "at {} line {}\n{}", self.file, self.line, fmt::indent("  ", "  ", &self.message))
   }
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn unwrap_should_bind_some_values() {
+    fn find(map: &[(&str, Option<i32>)], key: &str) -> Result<i32> {
+      let entry = unwrap!(map.iter().find(|(k, _)| *k == key));
+      let value = unwrap!(entry.1, "`{}` has no value", key);
+
+      Ok(value + 1)
+    }
+
+    let map = [("a", Some(1)), ("b", None)];
+
+    assert_eq!(find(&map, "a").unwrap(), 2);
+    assert_eq!(find(&map, "b").unwrap_err().message().as_str(), "`b` has no value");
+
+    let failure = find(&map, "c").unwrap_err();
+
+    assert!(failure
+      .message()
+      .starts_with("`map.iter().find(|(k, _)| *k == key)` is `None` on line "));
+    assert!(failure.message().ends_with(&format!(" of `{}`.", file!())));
+  }
+
+  #[test]
+  fn should_preserve_source_chain() {
+    let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+    let inner = failure!(io, "inner");
+    let outer = failure!(inner.clone(), "outer");
+
+    let source = outer.source().unwrap().downcast_ref::<Failure>().unwrap();
+
+    assert_eq!(source.message().as_str(), "inner");
+
+    let source = source.source().unwrap().downcast_ref::<std::io::Error>().unwrap();
+
+    assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+    assert!(source.source().is_none());
+  }
+
+  #[test]
+  fn fail_when_should_bind_ok_values() {
+    fn double(input: &str) -> Result<i32> {
+      failure::when!(let Ok(n) = input.parse::<i32>(), Err(err) => "Invalid number `{}`: {}", input, err);
+
+      Ok(n * 2)
+    }
+
+    assert_eq!(double("21").unwrap(), 42);
+    assert_eq!(
+      double("x").unwrap_err().message().as_str(),
+      "Invalid number `x`: invalid digit found in string"
+    );
+  }
+
+  #[test]
+  fn should_display_full_source_chain_when_alternate() {
+    #[derive(Debug, Error)]
+    #[error("outer io error")]
+    struct Wrapper(#[source] std::io::Error);
+
+    let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing file");
+    let (inner, line) = (failure!(Wrapper(io), "inner"), line!());
+    let failure = Failure::new("b.rs", 2, "outer", Some(inner));
+
+    let expected = format!(
+      "at b.rs line 2\n  outer\nat {} line {}\n  inner\n  outer io error\n  missing file",
+      file!(),
+      line
+    );
+
+    assert_eq!(format!("{:#}", failure), expected);
+    assert!(!failure.to_string().contains("missing file"));
+  }
+
+  #[test]
+  fn should_display_source_chain() {
+    let inner = Failure::new("a.rs", 1, "inner", None);
+    let outer = Failure::new("b.rs", 2, "outer", Some(inner));
+
+    assert_eq!(outer.to_string(), "at b.rs line 2\n  outer\nat a.rs line 1\n  inner");
+  }
+}
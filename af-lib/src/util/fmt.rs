@@ -9,10 +9,16 @@
 #[doc(no_inline)]
 pub use std::fmt::*;
 
+mod bytes;
 mod count;
 mod indent;
+mod relative;
 mod surround;
+mod table;
 
+pub use self::bytes::{bytes, Bytes};
 pub use self::count::{count, Counted};
 pub use self::indent::{indent, Indented, IndentedFormatter};
+pub use self::relative::{ago, relative, Relative};
 pub use self::surround::{surround, Surrounded};
+pub use self::table::Table;
@@ -0,0 +1,58 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::*;
+use crate::prelude::*;
+
+/// The units of [`bytes()`] in increasing order of size.
+const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB"];
+
+/// Wraps a number of bytes so that it displays in a human-readable unit; for
+/// example, `1.5 KiB` for `1536`.
+///
+/// The largest unit from B to TiB that keeps the value at least `1` is chosen,
+/// using a base of 1024, and the value is rounded to at most two decimal
+/// places.
+pub fn bytes(count: u64) -> Bytes {
+  Bytes(count)
+}
+
+/// A wrapper returned from [`bytes()`] that displays a number of bytes in a
+/// human-readable unit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Bytes(pub u64);
+
+impl Display for Bytes {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let mut value = self.0 as f64;
+    let mut unit = 0;
+
+    while unit + 1 < UNITS.len() && value.round_to_places(2) >= 1024.0 {
+      value /= 1024.0;
+      unit += 1;
+    }
+
+    write!(f, "{} {}", value.round_to_places(2), UNITS[unit])
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_choose_units() {
+    assert_eq!(bytes(0).to_string(), "0 B");
+    assert_eq!(bytes(1023).to_string(), "1023 B");
+    assert_eq!(bytes(1024).to_string(), "1 KiB");
+    assert_eq!(bytes(1536).to_string(), "1.5 KiB");
+    assert_eq!(bytes(3 * 1024 * 1024 * 1024 + 300 * 1024 * 1024).to_string(), "3.29 GiB");
+    assert_eq!(bytes(1024 * 1024 - 1).to_string(), "1 MiB");
+    assert_eq!(bytes(2048 * 1024u64.pow(4)).to_string(), "2048 TiB");
+  }
+}
@@ -0,0 +1,97 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::*;
+use crate::prelude::*;
+use crate::time::duration::DisplayUnit;
+use crate::time::DateTime;
+
+/// Wraps a date and time so that it displays relative to now; for example,
+/// `3 minutes ago` or `in 2 hours`.
+///
+/// This is shorthand for `relative(DateTime::now(), time)`.
+pub fn ago(time: DateTime) -> Relative {
+  relative(DateTime::now(), time)
+}
+
+/// Wraps a pair of dates and times so that `to` displays relative to `from`;
+/// for example, `3 minutes ago` or `in 2 hours`.
+///
+/// The difference is displayed as a whole number of the same units chosen by
+/// the [`Display`] implementation of [`Duration`][crate::time::Duration].
+/// Differences of less than two seconds are displayed as `just now`.
+pub fn relative(from: DateTime, to: DateTime) -> Relative {
+  Relative { from, to }
+}
+
+/// A wrapper returned from [`relative()`] or [`ago()`] that displays a date
+/// and time relative to another.
+#[derive(Clone, Copy, Debug)]
+pub struct Relative {
+  pub from: DateTime,
+  pub to: DateTime,
+}
+
+impl Display for Relative {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let (diff, future) = match self.to > self.from {
+      true => (self.to - self.from, true),
+      false => (self.from - self.to, false),
+    };
+
+    let unit = DisplayUnit::of(diff);
+
+    // Differences that a duration would display in milliseconds are too small
+    // to mention.
+
+    if unit.secs < 1.0 {
+      return write!(f, "just now");
+    }
+
+    let (one, many) = unit.words;
+    let n = count((diff.as_secs() / unit.secs).floor() as u64, one, many);
+
+    match future {
+      true => write!(f, "in {}", n),
+      false => write!(f, "{} ago", n),
+    }
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::time::Duration;
+
+  #[test]
+  fn should_display_past_times() {
+    let now = DateTime::now();
+
+    assert_eq!(relative(now, now - Duration::minutes(3)).to_string(), "3 minutes ago");
+    assert_eq!(relative(now, now - Duration::seconds(90)).to_string(), "90 seconds ago");
+    assert_eq!(relative(now, now - Duration::days(400)).to_string(), "1 year ago");
+  }
+
+  #[test]
+  fn should_display_future_times() {
+    let now = DateTime::now();
+
+    assert_eq!(relative(now, now + Duration::minutes(100)).to_string(), "in 100 minutes");
+    assert_eq!(relative(now, now + Duration::minutes(180)).to_string(), "in 3 hours");
+    assert_eq!(relative(now, now + Duration::days(3)).to_string(), "in 3 days");
+  }
+
+  #[test]
+  fn should_display_just_now() {
+    let now = DateTime::now();
+
+    assert_eq!(relative(now, now).to_string(), "just now");
+    assert_eq!(relative(now, now + Duration::seconds(1.5)).to_string(), "just now");
+    assert_eq!(ago(now - Duration::milliseconds(500)).to_string(), "just now");
+  }
+}
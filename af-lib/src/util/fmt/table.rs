@@ -0,0 +1,113 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::*;
+use crate::prelude::*;
+
+/// A table of cells that displays in aligned columns.
+///
+/// Each column is padded to the width of its widest cell and columns are
+/// separated by two spaces. If the table has a header, it is underlined with
+/// dashes.
+#[derive(Clone, Debug, Default)]
+pub struct Table {
+  header: Option<Vec<String>>,
+  rows: Vec<Vec<String>>,
+}
+
+impl Table {
+  /// Creates a new, empty table.
+  pub fn new() -> Self {
+    default()
+  }
+
+  /// Adds a row to the table.
+  pub fn add_row(&mut self, cells: impl IntoIterator<Item = impl Display>) -> &mut Self {
+    self.rows.push(cells.into_iter().map(|cell| cell.to_string()).collect());
+    self
+  }
+
+  /// Sets the header row of the table.
+  pub fn set_header(&mut self, cells: impl IntoIterator<Item = impl Display>) -> &mut Self {
+    self.header = Some(cells.into_iter().map(|cell| cell.to_string()).collect());
+    self
+  }
+}
+
+impl Display for Table {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    let rows = self.header.iter().chain(&self.rows);
+
+    // Measure the width of each column.
+
+    let mut widths: Vec<usize> = default();
+
+    for row in rows.clone() {
+      for (i, cell) in row.iter().enumerate() {
+        let width = cell.chars().count();
+
+        match widths.get_mut(i) {
+          Some(max) => *max = cmp::max(*max, width),
+          None => widths.push(width),
+        }
+      }
+    }
+
+    // Write each row, padding every cell except the last.
+
+    let write_row = |f: &mut Formatter, row: &[String]| -> fmt::Result {
+      for (i, cell) in row.iter().enumerate() {
+        match i + 1 == row.len() {
+          true => write!(f, "{}", cell)?,
+          false => write!(f, "{:width$}  ", cell, width = widths[i])?,
+        }
+      }
+
+      writeln!(f)
+    };
+
+    if let Some(header) = &self.header {
+      write_row(f, header)?;
+
+      let underline: Vec<_> = widths.iter().map(|width| "-".repeat(*width)).collect();
+
+      write_row(f, &underline)?;
+    }
+
+    for row in &self.rows {
+      write_row(f, row)?;
+    }
+
+    Ok(())
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_align_columns() {
+    let mut table = Table::new();
+
+    table.set_header(["name", "size"]);
+    table.add_row(["a.txt", "1 KiB"]).add_row(["archive.zip", "12 MiB"]);
+    table.add_row(vec![3.5, 12.0]);
+
+    assert_eq!(
+      table.to_string(),
+      concat!(
+        "name         size\n",
+        "-----------  ------\n",
+        "a.txt        1 KiB\n",
+        "archive.zip  12 MiB\n",
+        "3.5          12\n",
+      )
+    );
+  }
+}
@@ -0,0 +1,120 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::{Mutex, RwLock, RwLockReadGuard};
+
+use crate::prelude::*;
+
+/// Teardown functions of all initialized globals in order of initialization.
+static TEARDOWNS: Mutex<Vec<Box<dyn FnOnce() + Send>>> = Mutex::new(Vec::new());
+
+/// Initializes a global value that is dropped when the process exits normally.
+///
+/// The value is dropped after the main function returns, in reverse order of
+/// initialization, so any teardown work can be done in its [`Drop`]
+/// implementation. Unlike [`Lazy`], this guarantees teardown even if the
+/// returned handle is stored in a static.
+pub fn global<T>(init: impl FnOnce() -> T) -> Global<T>
+where
+  T: Send + Sync + 'static,
+{
+  let value = Arc::new(RwLock::new(Some(init())));
+  let teardown = value.clone();
+
+  TEARDOWNS.lock().unwrap().push(Box::new(move || {
+    let value = teardown.write().unwrap_or_else(|err| err.into_inner()).take();
+
+    drop(value);
+  }));
+
+  Global(value)
+}
+
+/// Drops all global values initialized with [`global()`].
+pub(crate) fn teardown() {
+  loop {
+    let teardown = TEARDOWNS.lock().unwrap().pop();
+
+    match teardown {
+      Some(teardown) => teardown(),
+      None => break,
+    }
+  }
+}
+
+/// A cloneable handle to a global value initialized with [`global()`].
+pub struct Global<T>(Arc<RwLock<Option<T>>>);
+
+impl<T> Global<T> {
+  /// Returns a reference to the value.
+  ///
+  /// The value cannot be torn down while the reference exists.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the value has already been torn down.
+  pub fn get(&self) -> GlobalRef<'_, T> {
+    self.try_get().expect("global value has been torn down")
+  }
+
+  /// Returns a reference to the value, or `None` if it has been torn down.
+  pub fn try_get(&self) -> Option<GlobalRef<'_, T>> {
+    let guard = self.0.read().unwrap_or_else(|err| err.into_inner());
+
+    guard.is_some().then(|| GlobalRef(guard))
+  }
+}
+
+impl<T> Clone for Global<T> {
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+/// A reference to a value returned from [`Global::get()`].
+pub struct GlobalRef<'a, T>(RwLockReadGuard<'a, Option<T>>);
+
+impl<T> Deref for GlobalRef<'_, T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    self.0.as_ref().unwrap()
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::AtomicBool;
+  use std::sync::atomic::Ordering::{Acquire, Release};
+
+  use super::*;
+
+  /// A value that sets a flag when it is dropped.
+  struct Guard {
+    torn_down: Arc<AtomicBool>,
+  }
+
+  impl Drop for Guard {
+    fn drop(&mut self) {
+      self.torn_down.store(true, Release);
+    }
+  }
+
+  #[test]
+  fn should_tear_down() {
+    let torn_down = Arc::new(AtomicBool::new(false));
+    let guard = global(|| Guard { torn_down: torn_down.clone() });
+
+    assert!(!guard.get().torn_down.load(Acquire));
+
+    teardown();
+
+    assert!(torn_down.load(Acquire));
+    assert!(guard.try_get().is_none());
+  }
+}
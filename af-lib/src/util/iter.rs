@@ -7,3 +7,148 @@
 //! Common [`Iterator`] types and utilities.
 
 pub use std::iter::*;
+
+use std::collections::VecDeque;
+
+/// Returns an iterator over non-overlapping chunks of `size` items.
+///
+/// The last chunk has fewer than `size` items if the number of items is not a
+/// multiple of `size`.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+pub fn chunked<I: IntoIterator>(iter: I, size: usize) -> impl Iterator<Item = Vec<I::Item>> {
+  assert!(size > 0, "the chunk size must be greater than zero");
+
+  let mut iter = iter.into_iter();
+
+  from_fn(move || {
+    let chunk: Vec<_> = iter.by_ref().take(size).collect();
+
+    match chunk.is_empty() {
+      true => None,
+      false => Some(chunk),
+    }
+  })
+}
+
+/// Returns an iterator that collapses consecutive items with equal keys into
+/// the first of those items.
+pub fn dedup_by_key<I, K>(
+  iter: I,
+  mut key: impl FnMut(&I::Item) -> K,
+) -> impl Iterator<Item = I::Item>
+where
+  I: IntoIterator,
+  K: PartialEq,
+{
+  let mut iter = iter.into_iter().peekable();
+
+  from_fn(move || {
+    let item = iter.next()?;
+    let item_key = key(&item);
+
+    while iter.next_if(|next| key(next) == item_key).is_some() {}
+
+    Some(item)
+  })
+}
+
+/// Returns an iterator that collapses consecutive equal items into one.
+pub fn dedup_consecutive<I>(iter: I) -> impl Iterator<Item = I::Item>
+where
+  I: IntoIterator,
+  I::Item: PartialEq,
+{
+  let mut iter = iter.into_iter().peekable();
+
+  from_fn(move || {
+    let item = iter.next()?;
+
+    while iter.next_if_eq(&item).is_some() {}
+
+    Some(item)
+  })
+}
+
+/// Returns an iterator over overlapping windows of `size` items.
+///
+/// Each window starts one item after the previous window, so each item is
+/// cloned into up to `size` windows. If there are fewer than `size` items, the
+/// iterator is empty.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+pub fn windows<I>(iter: I, size: usize) -> impl Iterator<Item = Vec<I::Item>>
+where
+  I: IntoIterator,
+  I::Item: Clone,
+{
+  assert!(size > 0, "the window size must be greater than zero");
+
+  let mut iter = iter.into_iter();
+  let mut window = VecDeque::with_capacity(size);
+
+  from_fn(move || {
+    if window.len() == size {
+      window.pop_front();
+    }
+
+    while window.len() < size {
+      window.push_back(iter.next()?);
+    }
+
+    Some(window.iter().cloned().collect())
+  })
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn chunked_should_split_exact_multiples() {
+    let chunks: Vec<_> = chunked(1..=4, 2).collect();
+
+    assert_eq!(chunks, [vec![1, 2], vec![3, 4]]);
+  }
+
+  #[test]
+  fn chunked_should_return_ragged_last_chunk() {
+    let chunks: Vec<_> = chunked(1..=5, 2).collect();
+
+    assert_eq!(chunks, [vec![1, 2], vec![3, 4], vec![5]]);
+  }
+
+  #[test]
+  #[should_panic(expected = "the chunk size must be greater than zero")]
+  fn chunked_should_panic_on_zero_size() {
+    let _ = chunked(1..=5, 0);
+  }
+
+  #[test]
+  fn dedup_consecutive_should_collapse_runs() {
+    let items: Vec<_> = dedup_consecutive(vec![1, 1, 2, 2, 2, 1]).collect();
+
+    assert_eq!(items, [1, 2, 1]);
+  }
+
+  #[test]
+  fn dedup_by_key_should_collapse_runs() {
+    let items: Vec<_> = dedup_by_key(vec!["a", "b", "cc", "dd", "e"], |s| s.len()).collect();
+
+    assert_eq!(items, ["a", "cc", "e"]);
+  }
+
+  #[test]
+  fn windows_should_overlap() {
+    let windows: Vec<_> = windows(1..=4, 3).collect();
+
+    assert_eq!(windows, [vec![1, 2, 3], vec![2, 3, 4]]);
+    assert_eq!(super::windows(1..=2, 3).count(), 0);
+  }
+}
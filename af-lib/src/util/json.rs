@@ -0,0 +1,83 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! JSON serialization and deserialization.
+//!
+//! This module re-exports [`serde_json`] and adds helpers for extracting values
+//! with JSON pointers.
+
+pub use serde_json::*;
+
+/// Returns the value at a JSON pointer in `value`, or `None` if there is no
+/// value at that location.
+///
+/// Pointers follow [RFC 6901](https://tools.ietf.org/html/rfc6901): each
+/// segment starts with `/`, names an object key or array index, and escapes `~`
+/// as `~0` and `/` as `~1`. The empty pointer refers to `value` itself.
+pub fn pointer<'a>(value: &'a Value, pointer: &str) -> Option<&'a Value> {
+  value.pointer(pointer)
+}
+
+/// Returns the integer at a JSON pointer in `value`, or `None` if there is no
+/// value at that location or it is not an integer that fits in an `i64`.
+pub fn pointer_i64(value: &Value, pointer: &str) -> Option<i64> {
+  self::pointer(value, pointer)?.as_i64()
+}
+
+/// Returns the string at a JSON pointer in `value`, or `None` if there is no
+/// value at that location or it is not a string.
+pub fn pointer_str<'a>(value: &'a Value, pointer: &str) -> Option<&'a str> {
+  self::pointer(value, pointer)?.as_str()
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn response() -> Value {
+    json!({
+      "ok": true,
+      "channel": { "id": "C123", "members": [7, 8, 9] },
+      "messages": [{ "text": "hello" }, { "text": "bye" }],
+      "a/b": 1,
+      "m~n": 2,
+    })
+  }
+
+  #[test]
+  fn should_extract_nested_values() {
+    let value = response();
+
+    assert_eq!(pointer(&value, ""), Some(&value));
+    assert_eq!(pointer(&value, "/ok"), Some(&Value::Bool(true)));
+    assert_eq!(pointer_str(&value, "/channel/id"), Some("C123"));
+    assert_eq!(pointer_i64(&value, "/a~1b"), Some(1));
+    assert_eq!(pointer_i64(&value, "/m~0n"), Some(2));
+  }
+
+  #[test]
+  fn should_extract_array_elements() {
+    let value = response();
+
+    assert_eq!(pointer_i64(&value, "/channel/members/0"), Some(7));
+    assert_eq!(pointer_i64(&value, "/channel/members/2"), Some(9));
+    assert_eq!(pointer_str(&value, "/messages/1/text"), Some("bye"));
+  }
+
+  #[test]
+  fn should_return_none_for_missing_or_mistyped_values() {
+    let value = response();
+
+    assert_eq!(pointer(&value, "/missing"), None);
+    assert_eq!(pointer(&value, "/channel/members/3"), None);
+    assert_eq!(pointer(&value, "/channel/members/-"), None);
+    assert_eq!(pointer(&value, "channel"), None);
+    assert_eq!(pointer_str(&value, "/channel/members/0"), None);
+    assert_eq!(pointer_i64(&value, "/channel/id"), None);
+  }
+}
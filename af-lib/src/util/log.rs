@@ -13,5 +13,6 @@ pub use log::{debug, error, info, trace, warn, Level};
 
 #[cfg(feature = "logger")]
 pub use self::logger::*;
+#[allow(ambiguous_glob_reexports)]
 pub use self::Level::*;
 use crate::prelude::*;
@@ -4,26 +4,78 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use std::any::Any;
 use std::cell::RefCell;
-use std::sync::atomic::AtomicUsize;
+use std::io;
 use std::sync::atomic::Ordering::Relaxed;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize};
+use std::sync::Mutex;
 use std::thread;
 
+pub use af_macros::log_span as span;
 pub use af_macros::logger_init as init;
 use dashmap::DashMap;
 use log::{Level, LevelFilter, Log, Metadata, Record, RecordBuilder};
 
 use super::*;
 use crate::concurrency::channel;
-use crate::time::DateTime;
+use crate::time::{DateTime, Duration, Instant};
 
 /// A logger to register with the `log` crate.
 struct Logger {
   dropped_messages: AtomicUsize,
+  format: AtomicUsize,
   max_level: AtomicUsize,
   max_level_of: DashMap<String, LevelFilter>,
+  output: Mutex<Box<dyn io::Write + Send>>,
+  output_failed: AtomicBool,
+  output_styled: AtomicBool,
   output_rx: channel::Receiver<Output>,
   output_tx: channel::BoundedSender<Output>,
+  sample_rate_of: DashMap<String, SampleRate>,
+  show_thread: AtomicBool,
+  started: Instant,
+}
+
+/// One of the possible formats of log messages.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+  /// The full format, which includes a timestamp and the name of the level.
+  ///
+  /// For example, `2021-06-01 12:00:00.000  WARN [target] message`.
+  Full,
+  /// A terse format, which includes the initial of the level and the time
+  /// elapsed since the logger was initialized.
+  ///
+  /// For example, `W +12.3s [target] message`.
+  Compact,
+  /// One JSON object per line without any styling, for log aggregators.
+  ///
+  /// Each object has `time` (RFC 3339), `level`, `target`, and `message`
+  /// fields, as well as a `thread` field if thread names are shown.
+  Json,
+}
+
+/// The rate at which messages from a specific module are sampled.
+struct SampleRate {
+  count: AtomicU32,
+  one_in: u32,
+  sampled_out: AtomicU64,
+}
+
+/// A guard returned from [`span!`] that removes its fields when dropped.
+///
+/// Inside a concurrency scope, the guard can be held across `.await` points
+/// and its fields follow the scope between threads. Outside of a scope, fields
+/// are thread-local, so they are only removed if the guard is dropped on the
+/// thread that entered the span.
+#[must_use = "the span ends when the guard is dropped"]
+pub struct Span {
+  /// The number of fields in the stack before the span was entered.
+  start: usize,
+  /// The thread that entered the span if it was entered outside of a
+  /// concurrency scope.
+  thread: Option<thread::ThreadId>,
 }
 
 /// One of the possible output commands.
@@ -38,16 +90,55 @@ static LOGGER: Lazy<Logger> = Lazy::new(|| {
 
   Logger {
     dropped_messages: default(),
+    format: AtomicUsize::new(Format::Full as usize),
     max_level: AtomicUsize::new(LevelFilter::Warn as usize),
     max_level_of: default(),
+    output: Mutex::new(Box::new(console::Term::stderr())),
+    output_failed: AtomicBool::new(false),
+    output_styled: AtomicBool::new(true),
     output_tx,
     output_rx,
+    sample_rate_of: default(),
+    show_thread: AtomicBool::new(false),
+    started: Instant::now(),
   }
 });
 
 thread_local! {
   /// A thread-local buffer for formatting messages.
   static THREAD_BUFFER: RefCell<String> = default();
+
+  /// A thread-local stack of fields from active spans, used outside of
+  /// concurrency scopes.
+  static THREAD_SPAN_FIELDS: RefCell<SpanFields> = default();
+}
+
+tokio::task_local! {
+  /// The stack of fields from active spans in the current concurrency scope.
+  static SCOPE_SPAN_FIELDS: RefCell<SpanFields>;
+}
+
+/// A stack of fields from active spans.
+type SpanFields = Vec<(&'static str, String)>;
+
+#[doc(hidden)]
+/// Enters a span with the given fields.
+///
+/// This function is used by the [`span!`] macro.
+pub fn enter_span(fields: &[(&'static str, &dyn Display)]) -> Span {
+  let thread = match SCOPE_SPAN_FIELDS.try_with(|_| ()) {
+    Ok(()) => None,
+    Err(_) => Some(thread::current().id()),
+  };
+
+  with_span_fields(|stack| {
+    let mut stack = stack.borrow_mut();
+    let start = stack.len();
+
+    stack.extend(fields.iter().map(|(key, value)| (*key, value.to_string())));
+
+    Span { start, thread }
+  })
 }
 
 #[doc(hidden)]
@@ -78,6 +169,26 @@ pub async fn flush() {
   rx.recv().await;
 }
 
+/// Returns the number of messages from a specific module that were discarded
+/// by sampling.
+///
+/// The count is kept for each module passed to [`set_sample_rate_of()`],
+/// including messages from its submodules, and is reset when its sample rate
+/// is set again.
+pub fn sampled_out_of(name: &str) -> u64 {
+  match LOGGER.sample_rate_of.get(name) {
+    Some(rate) => rate.sampled_out.load(Relaxed),
+    None => 0,
+  }
+}
+
+/// Sets the format of log messages.
+///
+/// The default format is [`Format::Full`].
+pub fn set_format(format: Format) {
+  LOGGER.format.store(format as usize, Relaxed);
+}
+
 /// Sets the current verbosity level.
 ///
 /// Set `level` to `None` to hide all messages. The verbosity of specific
@@ -98,11 +209,92 @@ pub fn set_level_of(name: impl Into<String>, level: impl Into<Option<Level>>) {
   LOGGER.max_level_of.insert(name, level);
 }
 
-/// Writes each message received from the given channel to stderr.
+/// Sets the writer that log messages are written to.
+///
+/// The default output is stderr. This function can be called before or after
+/// the logger is initialized; messages still waiting to be written when it is
+/// called are written to the new output.
+///
+/// Messages are only styled with colors if the writer is a
+/// [`console::Term`] attached to a terminal. If writing to the output fails,
+/// the error is reported to stderr once and the message is discarded.
+pub fn set_output(writer: impl io::Write + Send + 'static) {
+  let styled = match (&writer as &dyn Any).downcast_ref::<console::Term>() {
+    Some(term) => term.features().colors_supported(),
+    None => false,
+  };
+
+  *LOGGER.output.lock().unwrap() = Box::new(writer);
+
+  LOGGER.output_styled.store(styled, Relaxed);
+  LOGGER.output_failed.store(false, Relaxed);
+}
+
+/// Sets the rate at which messages from a specific module are sampled.
+///
+/// Only one of every `one_in` messages from the module is written and the rest
+/// are discarded and counted in [`sampled_out_of()`]. Set `one_in` to `1` or
+/// `0` to write every message.
+pub fn set_sample_rate_of(name: impl Into<String>, one_in: u32) {
+  let name = name.into();
+
+  match one_in > 1 {
+    true => {
+      LOGGER
+        .sample_rate_of
+        .insert(name, SampleRate { count: default(), one_in, sampled_out: default() });
+    }
+
+    false => {
+      LOGGER.sample_rate_of.remove(&name);
+    }
+  }
+}
+
+/// Sets whether to show the name of the thread that logged each message.
+///
+/// Threads without a name are shown by their ID.
+pub fn set_show_thread(show: bool) {
+  LOGGER.show_thread.store(show, Relaxed);
+}
+
+/// Finds the entry for a target or its closest parent module in a map.
+fn find_by_target<'a, V>(
+  map: &'a DashMap<String, V>,
+  target: &str,
+) -> Option<dashmap::mapref::one::Ref<'a, String, V>> {
+  let mut target = Some(target);
+
+  while let Some(t) = target {
+    if let Some(entry) = map.get(t) {
+      return Some(entry);
+    }
+
+    let mut split = t.rsplitn(2, "::");
+
+    split.next();
+
+    target = split.next();
+  }
+
+  None
+}
+
+/// Runs an async operation with a copy of the current span fields.
+///
+/// Spans entered in the operation do not affect the fields of the current
+/// context, even if both run on the same thread. This is used to give each
+/// concurrency scope its own fields.
+pub(crate) fn inherit_span_fields<F: Future>(op: F) -> impl Future<Output = F::Output> {
+  let fields = with_span_fields(|stack| stack.borrow().clone());
+
+  SCOPE_SPAN_FIELDS.scope(RefCell::new(fields), op)
+}
+
+/// Writes each message received from the given channel to the output.
 async fn output_messages() {
   let mut buffer = String::with_capacity(128);
   let logger = &*LOGGER;
-  let mut stderr = console::Term::stderr();
 
   while let Some(cmd) = logger.output_rx.recv().await {
     // If one or more messages were dropped, write an error message about it.
@@ -111,7 +303,11 @@ async fn output_messages() {
 
     if dropped_messages > 0 {
       write_message(
+        logger.format(),
         DateTime::now(),
+        logger.started.duration_since(),
+        None,
+        &[],
         &RecordBuilder::new()
           .level(Level::Error)
           .target(module_path!())
@@ -128,7 +324,7 @@ async fn output_messages() {
       )
       .unwrap();
 
-      writeln!(stderr, "{}", buffer).unwrap();
+      logger.write_output(&buffer);
 
       buffer.clear();
     }
@@ -136,9 +332,7 @@ async fn output_messages() {
     // Then run the command.
 
     match cmd {
-      Output::Write(message) => {
-        writeln!(stderr, "{}", message).unwrap();
-      }
+      Output::Write(message) => logger.write_output(&message),
 
       Output::Flush(tx) => {
         tx.send(());
@@ -150,35 +344,46 @@ async fn output_messages() {
 }
 
 /// Writes a record to the given string.
-fn write_message(time: DateTime, record: &Record, f: &mut String) -> fmt::Result {
-  use console::style;
-
-  // Write the timestamp in bright black.
-
-  write!(f, "{} ", style(time.format("%F %T%.3f")).black().bright())?;
-
-  // Write the log level with an appropriate color.
+fn write_message(
+  format: Format,
+  time: DateTime,
+  elapsed: Duration,
+  thread: Option<&str>,
+  fields: &[(&str, String)],
+  record: &Record,
+  f: &mut String,
+) -> fmt::Result {
+  use console::{style, Style};
+
+  // Write the log level with an appropriate color, preceded by the timestamp
+  // in bright black or followed by the elapsed time in bright black.
+
+  let (name, level_style) = match record.level() {
+    Level::Trace => ("TRACE", Style::new().black().bright()),
+    Level::Debug => ("DEBUG", Style::new().magenta()),
+    Level::Info => ("INFO", Style::new().blue()),
+    Level::Warn => ("WARN", Style::new().yellow()),
+    Level::Error => ("ERROR", Style::new().red()),
+  };
 
-  match record.level() {
-    Level::Trace => {
-      write!(f, "{} ", style("TRACE").black().bright())?;
+  match format {
+    Format::Full => {
+      write!(f, "{} ", style(time.format("%F %T%.3f")).black().bright())?;
+      write!(f, "{}{} ", &"     "[name.len()..], level_style.apply_to(name))?;
     }
 
-    Level::Debug => {
-      write!(f, "{} ", style("DEBUG").magenta())?;
+    Format::Compact => {
+      write!(f, "{} ", level_style.apply_to(&name[..1]))?;
+      write!(f, "{} ", style(format_args!("+{:.1}s", elapsed.as_secs())).black().bright())?;
     }
 
-    Level::Info => {
-      write!(f, " {} ", style("INFO").blue())?;
-    }
+    Format::Json => return write_json_message(time, thread, fields, record, f),
+  }
 
-    Level::Warn => {
-      write!(f, " {} ", style("WARN").yellow())?;
-    }
+  // Write the thread the message came from.
 
-    Level::Error => {
-      write!(f, "{} ", style("ERROR").red())?;
-    }
+  if let Some(thread) = thread {
+    write!(f, "{}", style(fmt::surround("(", thread, ") ")).black().bright())?;
   }
 
   // Write the source of the message.
@@ -203,34 +408,197 @@ fn write_message(time: DateTime, record: &Record, f: &mut String) -> fmt::Result
     _ => message,
   };
 
-  write!(f, "{}", styled)
+  write!(f, "{}", styled)?;
+
+  // Write the fields of active spans after the message.
+
+  for (key, value) in fields {
+    write!(f, " {}", style(format_args!("{}={}", key, value)).black().bright())?;
+  }
+
+  Ok(())
 }
 
-// Implement `Log` to send messages to the output task.
+/// Writes a record to the given string as a JSON object.
+fn write_json_message(
+  time: DateTime,
+  thread: Option<&str>,
+  fields: &[(&str, String)],
+  record: &Record,
+  f: &mut String,
+) -> fmt::Result {
+  write!(f, r#"{{"time":"{}","level":"{}","target":"#, time.as_rfc3339(), record.level())?;
+  write_json_string(record.target(), f)?;
+
+  if let Some(thread) = thread {
+    write!(f, r#","thread":"#)?;
+    write_json_string(thread, f)?;
+  }
 
-impl Log for Logger {
-  fn enabled(&self, metadata: &Metadata) -> bool {
-    let mut target = Some(metadata.target());
+  write!(f, r#","message":"#)?;
+  write_json_string(&record.args().to_string(), f)?;
+
+  if !fields.is_empty() {
+    write!(f, r#","fields":{{"#)?;
+
+    for (i, (key, value)) in fields.iter().enumerate() {
+      if i > 0 {
+        f.push(',');
+      }
+
+      write_json_string(key, f)?;
+      f.push(':');
+      write_json_string(value, f)?;
+    }
+
+    f.push('}');
+  }
+
+  write!(f, "}}")
+}
+
+/// Writes a string to the given string as a quoted and escaped JSON string.
+fn write_json_string(value: &str, f: &mut String) -> fmt::Result {
+  f.push('"');
+
+  for c in value.chars() {
+    match c {
+      '"' => f.push_str(r#"\""#),
+      '\\' => f.push_str(r"\\"),
+      '\n' => f.push_str(r"\n"),
+      '\r' => f.push_str(r"\r"),
+      '\t' => f.push_str(r"\t"),
+      c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+      c => f.push(c),
+    }
+  }
+
+  f.push('"');
+
+  Ok(())
+}
+
+/// Runs a closure with the stack of span fields of the current concurrency
+/// scope, or of the current thread if there is no scope.
+fn with_span_fields<T>(f: impl FnOnce(&RefCell<SpanFields>) -> T) -> T {
+  let mut f = Some(f);
+
+  SCOPE_SPAN_FIELDS
+    .try_with(|stack| f.take().unwrap()(stack))
+    .unwrap_or_else(|_| THREAD_SPAN_FIELDS.with(|stack| f.take().unwrap()(stack)))
+}
+
+impl Drop for Span {
+  fn drop(&mut self) {
+    let truncate = |stack: &RefCell<SpanFields>| stack.borrow_mut().truncate(self.start);
+
+    match self.thread {
+      None => {
+        let _ = SCOPE_SPAN_FIELDS.try_with(truncate);
+      }
+
+      Some(thread) if thread == thread::current().id() => THREAD_SPAN_FIELDS.with(truncate),
+
+      Some(_) => {}
+    }
+  }
+}
+
+impl Logger {
+  /// Returns the current message format.
+  fn format(&self) -> Format {
+    match self.format.load(Relaxed) {
+      x if x == Format::Compact as usize => Format::Compact,
+      x if x == Format::Json as usize => Format::Json,
+      _ => Format::Full,
+    }
+  }
+
+  /// Returns `true` if the next message from the given target should be
+  /// written according to its sample rate.
+  fn sample(&self, target: &str) -> bool {
+    match find_by_target(&self.sample_rate_of, target) {
+      Some(rate) => {
+        let sampled = rate.count.fetch_add(1, Relaxed).is_multiple_of(rate.one_in);
+
+        if !sampled {
+          rate.sampled_out.fetch_add(1, Relaxed);
+        }
+
+        sampled
+      }
 
-    while let Some(t) = target {
-      match self.max_level_of.get(t) {
-        Some(filter) => return metadata.level() <= *filter,
+      None => true,
+    }
+  }
+
+  /// Formats a record logged from the current thread.
+  fn format_record(&self, record: &Record) -> String {
+    let time = DateTime::now();
+    let elapsed = self.started.duration_since();
+    let current = thread::current();
 
-        None => {
-          let mut split = t.rsplitn(2, "::");
+    let thread: Option<Cow<str>> = match self.show_thread.load(Relaxed) {
+      false => None,
 
-          split.next();
+      true => match current.name() {
+        Some(name) => Some(name.into()),
+        None => Some(format!("{:?}", current.id()).into()),
+      },
+    };
 
-          target = split.next();
+    with_span_fields(|fields| {
+      THREAD_BUFFER.with(|buffer| {
+        let fields = fields.borrow();
+        let mut buffer = buffer.borrow_mut();
+
+        write_message(
+          self.format(),
+          time,
+          elapsed,
+          thread.as_deref(),
+          &fields,
+          record,
+          &mut buffer,
+        )
+        .unwrap();
+
+        let message = buffer.split_off(0);
+
+        match self.output_styled.load(Relaxed) {
+          true => message,
+          false => console::strip_ansi_codes(&message).into(),
         }
+      })
+    })
+  }
+
+  /// Writes a line to the output.
+  ///
+  /// If writing fails, the error is reported to stderr the first time only.
+  fn write_output(&self, line: &str) {
+    let result = writeln!(self.output.lock().unwrap(), "{}", line);
+
+    if let Err(err) = result {
+      if !self.output_failed.swap(true, Relaxed) {
+        eprintln!("Failed to write log messages: {}", err);
       }
     }
+  }
+}
 
-    metadata.level() as usize <= LOGGER.max_level.load(Relaxed)
+// Implement `Log` to send messages to the output task.
+
+impl Log for Logger {
+  fn enabled(&self, metadata: &Metadata) -> bool {
+    match find_by_target(&self.max_level_of, metadata.target()) {
+      Some(filter) => metadata.level() <= *filter,
+      None => metadata.level() as usize <= LOGGER.max_level.load(Relaxed),
+    }
   }
 
   fn log(&self, record: &Record) {
-    if !self.enabled(record.metadata()) {
+    if !self.enabled(record.metadata()) || !self.sample(record.target()) {
       return;
     }
 
@@ -239,20 +607,266 @@ impl Log for Logger {
       return;
     }
 
-    let time = DateTime::now();
+    let message = self.format_record(record);
+
+    if !self.output_tx.send_now(Output::Write(message)) {
+      LOGGER.dropped_messages.fetch_add(1, Relaxed);
+    }
+  }
+
+  fn flush(&self) {}
+}
 
-    let message = THREAD_BUFFER.with(|buffer| {
-      let mut buffer = buffer.borrow_mut();
+// Tests
 
-      write_message(time, record, &mut buffer).unwrap();
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-      buffer.split_off(0)
+  #[test]
+  fn should_sample_messages() {
+    set_sample_rate_of("sampled", 10);
+
+    let written = (0..100).filter(|_| LOGGER.sample("sampled::module")).count();
+
+    assert_eq!(sampled_out_of("sampled"), 90);
+
+    set_sample_rate_of("sampled", 1);
+
+    assert_eq!(written, 10);
+    assert_eq!(sampled_out_of("sampled"), 0);
+    assert!(LOGGER.sample("sampled::module"));
+    assert!(LOGGER.sample("other"));
+  }
+
+  #[test]
+  fn should_include_span_fields() {
+    let record = Record::builder().level(Level::Info).args(format_args!("hello")).build();
+
+    let (nested, outer) = {
+      let _request = span!(request = 7);
+
+      let nested = {
+        let _user = span!(user = "alex", admin = true);
+
+        LOGGER.format_record(&record)
+      };
+
+      (nested, LOGGER.format_record(&record))
+    };
+
+    assert!(console::strip_ansi_codes(&nested).ends_with("hello request=7 user=alex admin=true"));
+    assert!(console::strip_ansi_codes(&outer).ends_with("hello request=7"));
+    assert!(console::strip_ansi_codes(&LOGGER.format_record(&record)).ends_with("hello"));
+
+    let fields = [("request", "7".to_string()), ("user", "alex".to_string())];
+    let mut line = String::new();
+
+    write_message(Format::Json, DateTime::now(), Duration::ZERO, None, &fields, &record, &mut line)
+      .unwrap();
+
+    assert!(line.ends_with(r#""message":"hello","fields":{"request":"7","user":"alex"}}"#));
+  }
+
+  #[async_test]
+  async fn should_hold_span_across_await_in_task() {
+    use crate::concurrency::{join, task};
+
+    let (tx, rx) = channel();
+
+    task::start(async move {
+      let _span = span!(request = 7);
+
+      Duration::milliseconds(10).elapsed().await;
+
+      let record = Record::builder().level(Level::Info).args(format_args!("hello")).build();
+
+      tx.send(console::strip_ansi_codes(&LOGGER.format_record(&record)).into_owned());
     });
 
-    if !self.output_tx.send_now(Output::Write(message)) {
-      LOGGER.dropped_messages.fetch_add(1, Relaxed);
+    join().await;
+
+    assert!(rx.recv_now().unwrap().ends_with("hello request=7"));
+  }
+
+  #[async_test]
+  async fn should_keep_span_fields_separate_between_fibers() {
+    use crate::concurrency::{fiber, join};
+
+    fn format() -> String {
+      let record = Record::builder().level(Level::Info).args(format_args!("hello")).build();
+
+      console::strip_ansi_codes(&LOGGER.format_record(&record)).into()
     }
+
+    let (tx, rx) = channel();
+    let _parent = span!(parent = 1);
+
+    fiber::start({
+      let tx = tx.clone();
+
+      async move {
+        let span = span!(a = 1);
+
+        Duration::milliseconds(20).elapsed().await;
+        tx.send(format());
+
+        drop(span);
+      }
+    });
+
+    fiber::start(async move {
+      Duration::milliseconds(10).elapsed().await;
+
+      let _span = span!(b = 2);
+
+      Duration::milliseconds(20).elapsed().await;
+      tx.send(format());
+    });
+
+    join().await;
+
+    assert!(rx.recv_now().unwrap().ends_with("hello parent=1 a=1"));
+    assert!(rx.recv_now().unwrap().ends_with("hello parent=1 b=2"));
+    assert!(format().ends_with("hello parent=1"));
   }
 
-  fn flush(&self) {}
+  #[test]
+  fn should_show_thread_names() {
+    set_show_thread(true);
+
+    let lines: Vec<_> = ["worker-a", "worker-b"]
+      .iter()
+      .map(|name| {
+        thread::Builder::new()
+          .name(name.to_string())
+          .spawn(|| {
+            let record = Record::builder().level(Level::Info).args(format_args!("hello")).build();
+
+            LOGGER.format_record(&record)
+          })
+          .unwrap()
+          .join()
+          .unwrap()
+      })
+      .collect();
+
+    set_show_thread(false);
+
+    assert!(console::strip_ansi_codes(&lines[0]).ends_with("(worker-a) hello"));
+    assert!(console::strip_ansi_codes(&lines[1]).ends_with("(worker-b) hello"));
+  }
+
+  #[test]
+  fn should_write_compact_messages() {
+    let record =
+      Record::builder().level(Level::Warn).target("target").args(format_args!("message")).build();
+
+    let mut line = String::new();
+
+    write_message(
+      Format::Compact,
+      DateTime::now(),
+      Duration::milliseconds(12345),
+      None,
+      &[],
+      &record,
+      &mut line,
+    )
+    .unwrap();
+
+    assert_eq!(console::strip_ansi_codes(&line), "W +12.3s [target] message");
+  }
+
+  #[test]
+  fn should_write_json_messages() {
+    let message = "said \"hi\"\n\tdone\r\\ \u{1}\u{1f}\u{7f} ✓";
+
+    let fields = [("quote", "\"".to_string()), ("bell", "\u{7}".to_string())];
+    let time = DateTime::from_unix_ms(1_600_000_000_000);
+    let mut line = String::new();
+
+    write_message(
+      Format::Json,
+      time,
+      Duration::ZERO,
+      Some("main"),
+      &fields,
+      &Record::builder()
+        .level(Level::Info)
+        .target("app::db")
+        .args(format_args!("{}", message))
+        .build(),
+      &mut line,
+    )
+    .unwrap();
+
+    assert!(!line.contains('\n'));
+
+    let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+    let parsed_time = chrono::DateTime::parse_from_rfc3339(json["time"].as_str().unwrap()).unwrap();
+
+    assert_eq!(parsed_time.timestamp_millis(), 1_600_000_000_000);
+    assert_eq!(json["level"], "INFO");
+    assert_eq!(json["target"], "app::db");
+    assert_eq!(json["thread"], "main");
+    assert_eq!(json["message"], message);
+    assert_eq!(json["fields"]["quote"], "\"");
+    assert_eq!(json["fields"]["bell"], "\u{7}");
+  }
+
+  #[async_test]
+  async fn should_write_to_custom_output() {
+    /// A writer that appends to a shared buffer.
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+      fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+      }
+
+      fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+      }
+    }
+
+    let buffer = Arc::new(Mutex::new(Vec::new()));
+
+    set_output(Buffer(buffer.clone()));
+
+    unsafe { init() };
+
+    warn!("written to the custom output");
+    flush().await;
+
+    let output = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+
+    assert!(output.contains("written to the custom output\n"));
+    assert!(!output.contains('\x1b'));
+
+    // Errors from the output are reported once and do not stop the logger.
+
+    /// A writer that always fails.
+    struct Broken;
+
+    impl io::Write for Broken {
+      fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::ErrorKind::BrokenPipe.into())
+      }
+
+      fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+      }
+    }
+
+    set_output(Broken);
+
+    warn!("written to a broken output");
+    warn!("written to a broken output again");
+    flush().await;
+
+    assert!(LOGGER.output_failed.load(Relaxed));
+
+    set_output(console::Term::stderr());
+  }
 }
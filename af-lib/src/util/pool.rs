@@ -0,0 +1,124 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::Mutex;
+
+use crate::prelude::*;
+
+/// A cloneable pool of reusable items, such as buffers.
+///
+/// Items are created on demand and returned to the pool when the [`Pooled`]
+/// guard is dropped, so hot paths can reuse allocations instead of making new
+/// ones.
+pub struct Pool<T> {
+  inner: Arc<Inner<T>>,
+}
+
+/// The shared state of a pool.
+struct Inner<T> {
+  items: Mutex<Vec<T>>,
+  make: Box<dyn Fn() -> T + Send + Sync>,
+  reset: Box<dyn Fn(&mut T) + Send + Sync>,
+}
+
+/// An item borrowed from a [`Pool`].
+///
+/// When dropped, the item is reset and returned to the pool.
+pub struct Pooled<T> {
+  item: Option<T>,
+  pool: Arc<Inner<T>>,
+}
+
+impl<T> Pool<T> {
+  /// Creates a new, empty pool.
+  ///
+  /// The `make` function creates new items when the pool is empty, and the
+  /// `reset` function is called on each item before it is returned to the
+  /// pool.
+  pub fn new(
+    make: impl Fn() -> T + Send + Sync + 'static,
+    reset: impl Fn(&mut T) + Send + Sync + 'static,
+  ) -> Self {
+    Self {
+      inner: Arc::new(Inner { items: default(), make: Box::new(make), reset: Box::new(reset) }),
+    }
+  }
+
+  /// Takes an item from the pool, or creates a new one if the pool is empty.
+  pub fn get(&self) -> Pooled<T> {
+    let item = self.inner.items.lock().unwrap().pop().unwrap_or_else(|| (self.inner.make)());
+
+    Pooled { item: Some(item), pool: self.inner.clone() }
+  }
+
+  /// Returns `true` if the pool has no idle items.
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Returns the number of idle items in the pool.
+  pub fn len(&self) -> usize {
+    self.inner.items.lock().unwrap().len()
+  }
+}
+
+impl<T> Clone for Pool<T> {
+  fn clone(&self) -> Self {
+    Self { inner: self.inner.clone() }
+  }
+}
+
+impl<T> Deref for Pooled<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    self.item.as_ref().unwrap()
+  }
+}
+
+impl<T> DerefMut for Pooled<T> {
+  fn deref_mut(&mut self) -> &mut T {
+    self.item.as_mut().unwrap()
+  }
+}
+
+impl<T> Drop for Pooled<T> {
+  fn drop(&mut self) {
+    if let Some(mut item) = self.item.take() {
+      (self.pool.reset)(&mut item);
+
+      self.pool.items.lock().unwrap().push(item);
+    }
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_reuse_reset_items() {
+    let pool = Pool::new(Vec::<u8>::new, Vec::clear);
+
+    let mut buffer = pool.get();
+
+    buffer.extend_from_slice(&[0; 100]);
+
+    let capacity = buffer.capacity();
+
+    drop(buffer);
+
+    assert_eq!(pool.len(), 1);
+
+    let buffer = pool.get();
+
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.capacity(), capacity);
+    assert!(pool.is_empty());
+  }
+}
@@ -88,7 +88,7 @@ pub fn working_path() -> Result<String, WorkingPathError> {
 
   match path.into_os_string().into_string() {
     Ok(string) => Ok(string),
-    Err(path) => Err(WorkingPathError::NotUnicode(path.into())),
+    Err(path) => Err(WorkingPathError::NotUnicode(path)),
   }
 }
 
@@ -6,9 +6,45 @@
 
 //! Provides access to environment variables.
 
+use std::collections::BTreeMap;
 use std::ffi::OsString;
+use std::io;
 
+use crate::fs::path;
 use crate::prelude::*;
+use crate::util::process;
+
+/// Filters variables by a name prefix for [`vars_with_prefix()`].
+fn filter_prefix(
+  all_vars: impl IntoIterator<Item = (OsString, OsString)>,
+  prefix: &str,
+) -> BTreeMap<String, String> {
+  let mut vars = BTreeMap::new();
+
+  for (name, value) in all_vars {
+    let name = match name.into_string() {
+      Ok(name) => name,
+      Err(_) => continue,
+    };
+
+    let key = match name.strip_prefix(prefix) {
+      Some(key) => key,
+      None => continue,
+    };
+
+    match value.into_string() {
+      Ok(value) => {
+        vars.insert(key.into(), value);
+      }
+
+      Err(value) => {
+        warn!("Environment variable `{}` contains non-Unicode characters: `{:?}`.", name, value);
+      }
+    }
+  }
+
+  vars
+}
 
 /// Returns the value of an environment variable.
 pub fn get(name: &str) -> Result<String, GetError> {
@@ -24,6 +60,78 @@ pub fn get_os(name: &str) -> Option<OsString> {
   std::env::var_os(name)
 }
 
+/// Loads environment variables from a `.env` file.
+///
+/// If `path` is `None`, the `.env` file in the [project
+/// path](process::project_path) is loaded, or the one in the working directory
+/// if there is no project path.
+///
+/// Each line of the file is a `KEY=VALUE` pair, optionally prefixed with
+/// `export` and with the value in single or double quotes. Blank lines and
+/// lines starting with `#` are ignored. Variables that are already set are not
+/// overwritten.
+///
+/// This function returns the number of variables that were set.
+pub fn load_dotenv(path: Option<&str>) -> Result<usize, io::Error> {
+  let contents = match path {
+    Some(path) => std::fs::read_to_string(path)?,
+    None => match process::project_path() {
+      Some(project_path) => std::fs::read_to_string(path::join(project_path, ".env").as_ref())?,
+      None => std::fs::read_to_string(".env")?,
+    },
+  };
+
+  let vars = parse_dotenv(&contents, |name| std::env::var_os(name).is_some());
+
+  for (name, value) in &vars {
+    std::env::set_var(name, value);
+  }
+
+  Ok(vars.len())
+}
+
+/// Parses the contents of a `.env` file and returns the variables to set,
+/// skipping those for which `is_set` returns `true`.
+fn parse_dotenv(contents: &str, is_set: impl Fn(&str) -> bool) -> Vec<(&str, &str)> {
+  contents.lines().filter_map(parse_dotenv_line).filter(|(name, _)| !is_set(name)).collect()
+}
+
+/// Parses a `KEY=VALUE` line of a `.env` file.
+///
+/// Returns `None` if the line is blank, a comment, or not a valid assignment.
+fn parse_dotenv_line(line: &str) -> Option<(&str, &str)> {
+  let line = line.trim();
+
+  if line.starts_with('#') {
+    return None;
+  }
+
+  let line = line.strip_prefix("export ").map(str::trim_start).unwrap_or(line);
+  let (name, value) = line.split_once('=')?;
+  let name = name.trim();
+
+  if name.is_empty() || name.contains(char::is_whitespace) {
+    return None;
+  }
+
+  let value = value.trim();
+
+  let value = ['"', '\'']
+    .iter()
+    .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote))
+    .unwrap_or(value);
+
+  Some((name, value))
+}
+
+/// Returns all environment variables whose names start with `prefix`, keyed
+/// by name with the prefix removed.
+///
+/// Variables with non-Unicode values are skipped with a warning.
+pub fn vars_with_prefix(prefix: &str) -> BTreeMap<String, String> {
+  filter_prefix(std::env::vars_os(), prefix)
+}
+
 /// One of the possible errors returned when reading an environment variable.
 #[derive(Debug, Error)]
 pub enum GetError {
@@ -34,3 +142,75 @@ pub enum GetError {
   #[error("contains non-Unicode characters")]
   NotUnicode(OsString),
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::util::Uuid;
+
+  #[test]
+  fn should_parse_dotenv() {
+    let contents = concat!(
+      "# A comment.\n",
+      "\n",
+      "PLAIN=plain\n",
+      "export DOUBLE=\"double # quoted\"\n",
+      "SINGLE = 'single'\n",
+      "EXISTING=new\n",
+      "not an assignment\n",
+    );
+
+    let vars = parse_dotenv(contents, |name| name == "EXISTING");
+
+    assert_eq!(vars, [("PLAIN", "plain"), ("DOUBLE", "double # quoted"), ("SINGLE", "single")]);
+  }
+
+  #[test]
+  fn should_load_dotenv_file() {
+    let prefix = format!("AF_DOTENV_TEST_{}_", Uuid::new().to_string().replace('-', "_"));
+    let mut dir: String = std::env::temp_dir().to_str().unwrap().into();
+
+    path::append(&mut dir, &Uuid::new().to_string());
+    std::fs::create_dir(&dir).unwrap();
+
+    let first = path::join(&dir, "first.env");
+    let second = path::join(&dir, "second.env");
+
+    std::fs::write(first.as_ref(), format!("{}A=first\n", prefix)).unwrap();
+    std::fs::write(
+      second.as_ref(),
+      format!("# Comment.\n{0}A=second\nexport {0}B='b c'\n", prefix),
+    )
+    .unwrap();
+
+    let loaded_first = load_dotenv(Some(&first));
+    let loaded_second = load_dotenv(Some(&second));
+    let missing = load_dotenv(Some(&path::join(&dir, "missing.env")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(loaded_first.unwrap(), 1);
+    assert_eq!(loaded_second.unwrap(), 1);
+    assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+
+    let vars = vars_with_prefix(&prefix);
+
+    assert_eq!(vars.len(), 2);
+    assert_eq!(vars["A"], "first");
+    assert_eq!(vars["B"], "b c");
+  }
+
+  #[test]
+  fn should_collect_vars_with_prefix() {
+    let all_vars = [("TEST_PREFIX_ONE", "1"), ("TEST_PREFIX_TWO", "2"), ("OTHER", "3")];
+    let all_vars = all_vars.iter().map(|(name, value)| (name.into(), value.into()));
+
+    let vars = filter_prefix(all_vars, "TEST_PREFIX_");
+
+    assert_eq!(vars.len(), 2);
+    assert_eq!(vars["ONE"], "1");
+    assert_eq!(vars["TWO"], "2");
+  }
+}
@@ -108,6 +108,19 @@ impl Rng {
     })
   }
 
+  /// Creates a new `Rng` from a seed.
+  ///
+  /// Two `Rng` instances created from the same seed generate the same sequence
+  /// of values, which is useful for reproducible tests and simulations.
+  pub fn seeded(seed: u64) -> Rng {
+    Rng { inner: Xoshiro256StarStar::seed_from_u64(seed) }
+  }
+
+  /// Returns a random element of a slice, or `None` if it is empty.
+  pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+    slice.choose(&mut self.inner)
+  }
+
   /// Fills a slice with random bytes.
   pub fn fill_bytes(&mut self, bytes: &mut [u8]) {
     self.inner.fill(bytes);
@@ -150,3 +163,34 @@ impl Default for Rng {
     Self::new()
   }
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn seeded_rngs_should_be_reproducible() {
+    let mut a = Rng::seeded(42);
+    let mut b = Rng::seeded(42);
+    let mut c = Rng::seeded(43);
+
+    let a_values: Vec<u64> = (0..8).map(|_| a.gen()).collect();
+    let b_values: Vec<u64> = (0..8).map(|_| b.gen()).collect();
+    let c_values: Vec<u64> = (0..8).map(|_| c.gen()).collect();
+
+    assert_eq!(a_values, b_values);
+    assert_ne!(a_values, c_values);
+
+    let mut a_slice = [1, 2, 3, 4, 5, 6, 7, 8];
+    let mut b_slice = a_slice;
+
+    a.shuffle(&mut a_slice);
+    b.shuffle(&mut b_slice);
+
+    assert_eq!(a_slice, b_slice);
+    assert_eq!(a.gen_range(0..100), b.gen_range(0..100));
+    assert_eq!(a.choose(&a_slice), b.choose(&b_slice));
+  }
+}
@@ -4,8 +4,15 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use std::sync::Mutex;
+
+use rustc_hash::FxHashSet;
+
 use crate::prelude::*;
 
+/// The set of strings interned with [`SharedStr::intern()`].
+static INTERNED: Lazy<Mutex<FxHashSet<Arc<str>>>> = Lazy::new(default);
+
 /// A cheaply cloneable shared string.
 ///
 /// This type is useful when a value is likely to be a string literal (a
@@ -27,6 +34,25 @@ impl SharedStr {
       Inner::StaticStr(value) => value,
     }
   }
+
+  /// Returns a shared string equal to `value` that shares its allocation with
+  /// all other interned strings that are equal to it.
+  ///
+  /// Interned strings are never freed, so this function is best used for a
+  /// bounded set of strings such as keys and labels.
+  pub fn intern(value: &str) -> Self {
+    let mut interned = INTERNED.lock().unwrap();
+
+    if let Some(existing) = interned.get(value) {
+      return Self(Inner::ArcStr(existing.clone()));
+    }
+
+    let value: Arc<str> = value.into();
+
+    interned.insert(value.clone());
+
+    Self(Inner::ArcStr(value))
+  }
 }
 
 impl AsRef<str> for SharedStr {
@@ -35,6 +61,12 @@ impl AsRef<str> for SharedStr {
   }
 }
 
+impl Borrow<str> for SharedStr {
+  fn borrow(&self) -> &str {
+    self.as_str()
+  }
+}
+
 impl Display for SharedStr {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     Display::fmt(self.as_str(), f)
@@ -129,9 +161,21 @@ impl PartialEq for SharedStr {
   }
 }
 
+impl PartialEq<str> for SharedStr {
+  fn eq(&self, other: &str) -> bool {
+    self.as_str() == other
+  }
+}
+
+impl PartialEq<&str> for SharedStr {
+  fn eq(&self, other: &&str) -> bool {
+    self.as_str() == *other
+  }
+}
+
 impl PartialOrd for SharedStr {
   fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-    self.as_str().partial_cmp(other.as_str())
+    Some(self.cmp(other))
   }
 }
 
@@ -143,3 +187,37 @@ impl Serialize for SharedStr {
     self.as_str().serialize(serializer)
   }
 }
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_equal_strs() {
+    let value = SharedStr::from(String::from("label"));
+
+    assert_eq!(value, "label");
+    assert!(value == *"label");
+    assert_ne!(value, "other");
+  }
+
+  #[test]
+  fn clone_should_share_allocation() {
+    let value = SharedStr::from(String::from("label"));
+
+    assert_eq!(value.clone().as_ptr(), value.as_ptr());
+  }
+
+  #[test]
+  fn intern_should_share_allocation() {
+    let a = SharedStr::intern(&String::from("interned"));
+    let b = SharedStr::intern(&String::from("interned"));
+    let c = SharedStr::intern("other");
+
+    assert_eq!(a, b);
+    assert_eq!(a.as_ptr(), b.as_ptr());
+    assert_ne!(a.as_ptr(), c.as_ptr());
+  }
+}
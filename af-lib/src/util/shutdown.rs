@@ -0,0 +1,100 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::concurrency::future::{Context, Poll};
+use crate::concurrency::{runtime, Latch};
+use crate::prelude::*;
+
+/// A cloneable coordinator that broadcasts a stop signal to many components.
+///
+/// Components [subscribe](Self::subscribe) and race their work against the
+/// returned future, which resolves once the shutdown is triggered.
+#[derive(Clone, Default)]
+pub struct Shutdown {
+  latch: Latch<()>,
+}
+
+impl Shutdown {
+  /// Creates a new shutdown coordinator that has not been triggered.
+  pub fn new() -> Self {
+    default()
+  }
+
+  /// Returns a shutdown coordinator that is triggered when the process
+  /// receives a shutdown signal.
+  ///
+  /// All coordinators returned from this function are shared, and triggering
+  /// one also resolves [`runtime::shutdown_signal()`].
+  pub fn on_signal() -> Self {
+    Self { latch: runtime::signal_latch() }
+  }
+
+  /// Returns `true` if the shutdown was triggered.
+  pub fn is_triggered(&self) -> bool {
+    self.latch.is_set()
+  }
+
+  /// Returns a future that resolves when the shutdown is triggered.
+  pub fn subscribe(&self) -> Subscription {
+    Subscription { latch: self.latch.clone(), wait: None }
+  }
+
+  /// Triggers the shutdown, resolving all subscriptions.
+  ///
+  /// This function returns `true` if the shutdown was triggered or `false` if
+  /// it was already triggered.
+  pub fn trigger(&self) -> bool {
+    self.latch.set(())
+  }
+}
+
+/// A cloneable future returned from [`Shutdown::subscribe()`] that resolves
+/// when the shutdown is triggered.
+pub struct Subscription {
+  latch: Latch<()>,
+  wait: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+}
+
+impl Clone for Subscription {
+  fn clone(&self) -> Self {
+    Self { latch: self.latch.clone(), wait: None }
+  }
+}
+
+impl Future for Subscription {
+  type Output = ();
+
+  fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+    if self.latch.is_set() {
+      return Poll::Ready(());
+    }
+
+    let latch = self.latch.clone();
+
+    self.wait.get_or_insert_with(|| Box::pin(async move { latch.get().await })).as_mut().poll(cx)
+  }
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::concurrency::future;
+
+  #[async_test]
+  async fn should_resolve_all_subscribers() {
+    let shutdown = Shutdown::new();
+    let a = shutdown.subscribe();
+    let b = a.clone();
+
+    assert!(!shutdown.is_triggered());
+    assert!(shutdown.clone().trigger());
+    assert!(!shutdown.trigger());
+
+    future::join_all(vec![a, b, shutdown.subscribe()]).await;
+  }
+}
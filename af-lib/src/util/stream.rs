@@ -7,3 +7,286 @@
 //! Common [`Stream`] types and utilities.
 
 pub use futures_lite::stream::*;
+
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_lite::ready;
+use pin_project::pin_project;
+
+use crate::concurrency::future::{self, Future};
+use crate::time::{DateTime, Duration, Instant};
+
+/// An extension trait for streams that adds time-based combinators.
+pub trait StreamTimeExt: Stream + Sized {
+  /// Returns a stream that collects items into batches.
+  ///
+  /// A batch is yielded when it reaches `max_size` items or when `max_delay`
+  /// elapses after its first item, whichever comes first. When this stream
+  /// ends, any remaining items are yielded as a final batch.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `max_size` is zero.
+  fn chunks(self, max_size: usize, max_delay: Duration) -> Chunks<Self> {
+    assert!(max_size > 0, "the maximum chunk size must be greater than zero");
+
+    Chunks { stream: self, max_size, max_delay, buffer: Vec::new(), timer: None, is_done: false }
+  }
+
+  /// Returns a stream that yields an item only after `quiet` elapses without
+  /// a newer item.
+  ///
+  /// Items that are followed by a newer item within `quiet` are dropped. When
+  /// this stream ends, the last pending item is yielded immediately.
+  fn debounce(self, quiet: Duration) -> Debounce<Self> {
+    Debounce { stream: self, quiet, pending: None, timer: None, is_done: false }
+  }
+
+  /// Returns a stream that yields at most one item per `min_interval`.
+  ///
+  /// The first item is yielded immediately, and items received less than
+  /// `min_interval` after the last yielded item are dropped.
+  fn throttle(self, min_interval: Duration) -> Throttle<Self> {
+    Throttle { stream: self, min_interval, last: None }
+  }
+}
+
+impl<S: Stream> StreamTimeExt for S {}
+
+/// A stream returned from [`StreamTimeExt::chunks()`].
+#[pin_project]
+pub struct Chunks<S: Stream> {
+  #[pin]
+  stream: S,
+  max_size: usize,
+  max_delay: Duration,
+  buffer: Vec<S::Item>,
+  timer: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+  is_done: bool,
+}
+
+impl<S: Stream> Stream for Chunks<S> {
+  type Item = Vec<S::Item>;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+    let mut this = self.project();
+
+    // Buffer all available items, starting the timer with the first one.
+
+    while !*this.is_done {
+      match this.stream.as_mut().poll_next(cx) {
+        Poll::Ready(Some(item)) => {
+          if this.buffer.is_empty() {
+            let max_delay = *this.max_delay;
+
+            *this.timer = Some(Box::pin(async move { max_delay.elapsed().await }));
+          }
+
+          this.buffer.push(item);
+
+          if this.buffer.len() >= *this.max_size {
+            *this.timer = None;
+
+            return Poll::Ready(Some(mem::take(this.buffer)));
+          }
+        }
+
+        Poll::Ready(None) => *this.is_done = true,
+        Poll::Pending => break,
+      }
+    }
+
+    if *this.is_done {
+      *this.timer = None;
+
+      return match this.buffer.is_empty() {
+        true => Poll::Ready(None),
+        false => Poll::Ready(Some(mem::take(this.buffer))),
+      };
+    }
+
+    match this.timer {
+      Some(timer) => {
+        ready!(timer.as_mut().poll(cx));
+
+        *this.timer = None;
+
+        Poll::Ready(Some(mem::take(this.buffer)))
+      }
+
+      None => Poll::Pending,
+    }
+  }
+}
+
+/// A stream returned from [`StreamTimeExt::debounce()`].
+#[pin_project]
+pub struct Debounce<S: Stream> {
+  #[pin]
+  stream: S,
+  quiet: Duration,
+  pending: Option<S::Item>,
+  timer: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+  is_done: bool,
+}
+
+impl<S: Stream> Stream for Debounce<S> {
+  type Item = S::Item;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+    let mut this = self.project();
+
+    // Take all available items, restarting the timer for each one.
+
+    while !*this.is_done {
+      match this.stream.as_mut().poll_next(cx) {
+        Poll::Ready(Some(item)) => {
+          let quiet = *this.quiet;
+
+          *this.pending = Some(item);
+          *this.timer = Some(Box::pin(async move { quiet.elapsed().await }));
+        }
+
+        Poll::Ready(None) => *this.is_done = true,
+        Poll::Pending => break,
+      }
+    }
+
+    if *this.is_done {
+      return Poll::Ready(this.pending.take());
+    }
+
+    match this.timer {
+      Some(timer) => {
+        ready!(timer.as_mut().poll(cx));
+
+        *this.timer = None;
+
+        Poll::Ready(this.pending.take())
+      }
+
+      None => Poll::Pending,
+    }
+  }
+}
+
+/// A stream returned from [`StreamTimeExt::throttle()`].
+#[pin_project]
+pub struct Throttle<S> {
+  #[pin]
+  stream: S,
+  min_interval: Duration,
+  last: Option<Instant>,
+}
+
+impl<S: Stream> Stream for Throttle<S> {
+  type Item = S::Item;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+    let mut this = self.project();
+
+    loop {
+      let item = ready!(this.stream.as_mut().poll_next(cx));
+      let min_interval = *this.min_interval;
+
+      if item.is_none() || this.last.is_none_or(|last| last.duration_since() >= min_interval) {
+        *this.last = Some(Instant::now());
+
+        return Poll::Ready(item);
+      }
+    }
+  }
+}
+
+/// Returns a stream that yields the current date and time once every `period`.
+///
+/// The first tick occurs one period after this function is called. Each tick is
+/// scheduled relative to when the previous one was due rather than when it was
+/// handled, so the ticks do not drift if the consumer is slow. If `period` is
+/// infinite, the stream never yields.
+pub fn interval(period: Duration) -> impl Stream<Item = DateTime> {
+  unfold(Instant::now(), move |mut next| async move {
+    if period.is_infinite() {
+      future::never().await;
+    }
+
+    next += period;
+    next.occurred().await;
+
+    Some((DateTime::now(), next))
+  })
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::concurrency::{channel, fiber};
+  use crate::prelude::*;
+
+  /// Returns a stream of messages sent at the given millisecond offsets.
+  fn timed_stream(items: &'static [(usize, f64)]) -> impl Stream<Item = usize> {
+    let (tx, rx) = channel();
+
+    fiber::start(async move {
+      for &(item, delay) in items {
+        Duration::milliseconds(delay).elapsed().await;
+        tx.send(item);
+      }
+    });
+
+    unfold(rx, |rx| async move { Some((rx.recv().await?, rx)) })
+  }
+
+  #[async_test]
+  async fn chunks_should_flush_when_full() {
+    let chunks: Vec<_> = iter(1..=5).chunks(2, Duration::forever()).collect().await;
+
+    assert_eq!(chunks, [vec![1, 2], vec![3, 4], vec![5]]);
+  }
+
+  #[async_test]
+  async fn chunks_should_flush_after_delay() {
+    let items = timed_stream(&[(1, 0.0), (2, 0.0), (3, 50.0), (4, 0.0)]);
+    let chunks: Vec<_> = items.chunks(10, Duration::milliseconds(20)).collect().await;
+
+    assert_eq!(chunks, [vec![1, 2], vec![3, 4]]);
+  }
+
+  #[async_test]
+  async fn debounce_should_wait_for_quiet() {
+    let items = timed_stream(&[(1, 0.0), (2, 0.0), (3, 50.0), (4, 50.0), (5, 0.0)]);
+    let debounced: Vec<_> = items.debounce(Duration::milliseconds(20)).collect().await;
+
+    assert_eq!(debounced, [2, 3, 5]);
+  }
+
+  #[async_test]
+  async fn interval_should_not_drift() {
+    let started = Instant::now();
+    let ticks = interval(Duration::milliseconds(30));
+
+    pin!(ticks);
+
+    for _ in 0..3 {
+      ticks.next().await;
+      Duration::milliseconds(25).elapsed().await;
+    }
+
+    let elapsed = started.duration_since();
+
+    assert!(elapsed >= Duration::milliseconds(115), "elapsed {:?}", elapsed);
+    assert!(elapsed < Duration::milliseconds(160), "elapsed {:?}", elapsed);
+  }
+
+  #[async_test]
+  async fn throttle_should_drop_items() {
+    let items = timed_stream(&[(1, 0.0), (2, 0.0), (3, 40.0), (4, 0.0), (5, 40.0)]);
+    let throttled: Vec<_> = items.throttle(Duration::milliseconds(20)).collect().await;
+
+    assert_eq!(throttled, [1, 3, 5]);
+  }
+}
@@ -0,0 +1,290 @@
+// Copyright © 2021 Alexandra Frydl
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! String manipulation utilities.
+
+use crate::prelude::*;
+
+/// A table of common English words with irregular plural forms.
+///
+/// Each entry is a `(singular, plural)` pair. Custom tables can be used with
+/// [`pluralize_with()`].
+pub const IRREGULARS: &[(&str, &str)] = &[
+  ("child", "children"),
+  ("foot", "feet"),
+  ("goose", "geese"),
+  ("man", "men"),
+  ("mouse", "mice"),
+  ("ox", "oxen"),
+  ("person", "people"),
+  ("tooth", "teeth"),
+  ("woman", "women"),
+];
+
+/// Returns a line-by-line diff between two strings.
+///
+/// Each line of the output is prefixed with `-` if it was removed from `old`,
+/// `+` if it was added in `new`, or a space if it is unchanged. Lines are
+/// matched using the longest common subsequence, and removals are listed
+/// before additions where both occur.
+pub fn diff_lines(old: &str, new: &str) -> String {
+  let old: Vec<&str> = old.lines().collect();
+  let new: Vec<&str> = new.lines().collect();
+
+  // Compute the length of the longest common subsequence of every pair of
+  // suffixes.
+
+  let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+
+  for i in (0..old.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lcs[i][j] = match old[i] == new[j] {
+        true => lcs[i + 1][j + 1] + 1,
+        false => lcs[i + 1][j].max(lcs[i][j + 1]),
+      };
+    }
+  }
+
+  // Walk the table, emitting unchanged lines where possible.
+
+  let mut output = String::new();
+  let (mut i, mut j) = (0, 0);
+
+  while i < old.len() || j < new.len() {
+    if i < old.len() && j < new.len() && old[i] == new[j] {
+      writeln!(output, " {}", old[i]).unwrap();
+      i += 1;
+      j += 1;
+    } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+      writeln!(output, "-{}", old[i]).unwrap();
+      i += 1;
+    } else {
+      writeln!(output, "+{}", new[j]).unwrap();
+      j += 1;
+    }
+  }
+
+  output
+}
+
+/// Wraps each occurrence of `query` in `text` using the given function.
+///
+/// Occurrences are matched case-insensitively and only on word boundaries, so
+/// `"cat"` matches in `"a Cat sat"` but not in `"concatenate"`. Matches are
+/// found from left to right and never overlap; the text of each match is
+/// passed to `wrap` with its original casing.
+pub fn highlight(text: &str, query: &str, wrap: impl Fn(&str) -> String) -> String {
+  if query.is_empty() {
+    return text.into();
+  }
+
+  let is_word_char = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+  let mut output = String::with_capacity(text.len());
+  let mut copied = 0;
+  let mut start = 0;
+
+  while start < text.len() {
+    let rest = &text[start..];
+    let before = text[..start].chars().next_back();
+
+    // Compare the query to the text at this position one character at a time.
+
+    let mut chars = rest.char_indices();
+
+    let matches = query
+      .chars()
+      .all(|q| chars.next().is_some_and(|(_, c)| c.to_lowercase().eq(q.to_lowercase())));
+
+    let len = chars.next().map(|(end, _)| end).unwrap_or(rest.len());
+
+    match matches && !is_word_char(before) && !is_word_char(rest[len..].chars().next()) {
+      true => {
+        output.push_str(&text[copied..start]);
+        output.push_str(&wrap(&rest[..len]));
+
+        start += len;
+        copied = start;
+      }
+
+      false => start += rest.chars().next().map(char::len_utf8).unwrap_or(1),
+    }
+  }
+
+  output.push_str(&text[copied..]);
+  output
+}
+
+/// Compares two strings in natural order.
+///
+/// Runs of ASCII digits are compared by their numeric value and all other
+/// characters are compared lexically, so `"item2"` comes before `"item10"`.
+/// Numbers that are equal but have different leading zeros compare as equal.
+pub fn natural_cmp(a: &str, b: &str) -> cmp::Ordering {
+  let mut a = a.chars().peekable();
+  let mut b = b.chars().peekable();
+
+  loop {
+    let (x, y) = match (a.peek(), b.peek()) {
+      (None, None) => return cmp::Ordering::Equal,
+      (None, Some(_)) => return cmp::Ordering::Less,
+      (Some(_), None) => return cmp::Ordering::Greater,
+      (Some(&x), Some(&y)) => (x, y),
+    };
+
+    if !x.is_ascii_digit() || !y.is_ascii_digit() {
+      match x.cmp(&y) {
+        cmp::Ordering::Equal => {
+          a.next();
+          b.next();
+          continue;
+        }
+
+        ordering => return ordering,
+      }
+    }
+
+    // Compare runs of digits by length without leading zeros, then lexically.
+
+    let take_digits = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+      let mut digits = String::new();
+
+      while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+      }
+
+      digits.trim_start_matches('0').to_string()
+    };
+
+    let x = take_digits(&mut a);
+    let y = take_digits(&mut b);
+
+    match x.len().cmp(&y.len()).then_with(|| x.cmp(&y)) {
+      cmp::Ordering::Equal => continue,
+      ordering => return ordering,
+    }
+  }
+}
+
+/// Sorts a slice of strings in natural order.
+///
+/// See [`natural_cmp()`] for details.
+pub fn natural_sort(strings: &mut [String]) {
+  strings.sort_by(|a, b| natural_cmp(a, b));
+}
+
+/// Returns the form of an English word appropriate for a count of `n`.
+///
+/// If `n` is `1`, the word is returned unchanged. Otherwise, the plural is
+/// looked up in [`IRREGULARS`] or formed using common English rules.
+pub fn pluralize(word: &str, n: usize) -> String {
+  pluralize_with(word, n, IRREGULARS)
+}
+
+/// Returns the form of an English word appropriate for a count of `n` using a
+/// custom table of irregular `(singular, plural)` pairs.
+///
+/// The table is searched before common English rules are applied, so it can
+/// also be used to override the result for regular words.
+pub fn pluralize_with(word: &str, n: usize, irregulars: &[(&str, &str)]) -> String {
+  if n == 1 || word.is_empty() {
+    return word.into();
+  }
+
+  // Check the table of irregular plurals first, preserving an initial capital.
+
+  let lowercase = word.to_lowercase();
+
+  if let Some((_, plural)) = irregulars.iter().find(|(singular, _)| *singular == lowercase) {
+    return match word.chars().next() {
+      Some(c) if c.is_uppercase() => {
+        let mut chars = plural.chars();
+        chars.next().into_iter().flat_map(char::to_uppercase).chain(chars).collect()
+      }
+
+      _ => (*plural).into(),
+    };
+  }
+
+  // Otherwise apply common suffix rules.
+
+  let ends_with_consonant_y = lowercase.ends_with('y')
+    && !lowercase[..lowercase.len() - 1].ends_with(['a', 'e', 'i', 'o', 'u']);
+
+  if ends_with_consonant_y {
+    return format!("{}ies", &word[..word.len() - 1]);
+  }
+
+  if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| lowercase.ends_with(suffix)) {
+    return format!("{}es", word);
+  }
+
+  format!("{}s", word)
+}
+
+// Tests
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn should_diff_lines() {
+    let old = "one\ntwo\nthree\nfour\n";
+    let new = "one\n2\nthree\nfour\nfive\n";
+
+    assert_eq!(diff_lines(old, new), " one\n-two\n+2\n three\n four\n+five\n");
+    assert_eq!(diff_lines("a\nb", "a\nb"), " a\n b\n");
+    assert_eq!(diff_lines("a\nb", ""), "-a\n-b\n");
+  }
+
+  #[test]
+  fn should_highlight_words() {
+    let bold = |s: &str| format!("*{}*", s);
+
+    assert_eq!(
+      highlight("A cat, a Cat, a concatenation.", "cat", bold),
+      "A *cat*, a *Cat*, a concatenation."
+    );
+    assert_eq!(highlight("cats and cat", "cat", bold), "cats and *cat*");
+    assert_eq!(highlight("aa aa", "aa", bold), "*aa* *aa*");
+    assert_eq!(highlight("text", "", bold), "text");
+  }
+
+  #[test]
+  fn should_sort_naturally() {
+    let mut strings = vec!["item10".to_string(), "item2".into(), "item1".into()];
+
+    natural_sort(&mut strings);
+
+    assert_eq!(strings, ["item1", "item2", "item10"]);
+    assert_eq!(natural_cmp("a02b", "a2b"), cmp::Ordering::Equal);
+    assert_eq!(natural_cmp("a2b", "a2c"), cmp::Ordering::Less);
+    assert_eq!(natural_cmp("file", "file1"), cmp::Ordering::Less);
+  }
+
+  #[test]
+  fn should_pluralize_regular_words() {
+    assert_eq!(pluralize("cat", 2), "cats");
+    assert_eq!(pluralize("cat", 1), "cat");
+    assert_eq!(pluralize("cat", 0), "cats");
+    assert_eq!(pluralize("day", 2), "days");
+  }
+
+  #[test]
+  fn should_pluralize_suffixes() {
+    assert_eq!(pluralize("city", 2), "cities");
+    assert_eq!(pluralize("box", 2), "boxes");
+    assert_eq!(pluralize("church", 2), "churches");
+    assert_eq!(pluralize("bus", 2), "buses");
+  }
+
+  #[test]
+  fn should_pluralize_irregulars() {
+    assert_eq!(pluralize("person", 2), "people");
+    assert_eq!(pluralize("Person", 2), "People");
+    assert_eq!(pluralize_with("cactus", 2, &[("cactus", "cacti")]), "cacti");
+  }
+}
@@ -8,11 +8,11 @@
 #[macro_export]
 macro_rules! failure {
   ($cause:expr, $msg:literal, $($arg:tt)+) => {
-    Failure::new(file!(), line!(), format!($msg, $($arg)+), Some($cause))
+    Failure::with_source(file!(), line!(), format!($msg, $($arg)+), $cause)
   };
 
   ($cause:expr, $msg:literal) => {
-    Failure::new(file!(), line!(), $msg, Some($cause))
+    Failure::with_source(file!(), line!(), $msg, $cause)
   };
 
   ($msg:literal, $($arg:tt)+) => {
@@ -36,9 +36,34 @@ macro_rules! fail {
   }
 }
 
+/// Unwraps an `Option`, returning an `Err(Failure)` from the current function
+/// if it is `None`.
+///
+/// Without a message, the failure names the expression and the line it is on.
+#[macro_export]
+macro_rules! fail_unwrap {
+  ($expr:expr) => {
+    match $expr {
+      Some(value) => value,
+      None => fail!("`{}` is `None` on line {} of `{}`.", stringify!($expr), line!(), file!()),
+    }
+  };
+
+  ($expr:expr, $($args:tt)+) => {
+    match $expr {
+      Some(value) => value,
+      None => fail!($($args)+),
+    }
+  };
+}
+
 /// Returns a `fail::Error` if a condition is `true`.
 ///
 /// Some simple patterns have default error messages.
+///
+/// The form `let Ok(value) = expr, Err(err) => "message", ...` binds `value`
+/// in the enclosing scope on success and fails with the message otherwise,
+/// where `err` is available to the message arguments.
 #[macro_export]
 macro_rules! fail_when {
   (let Err($err:ident) = $value:ident, $($args:tt)+) => {
@@ -48,6 +73,13 @@ macro_rules! fail_when {
     };
   };
 
+  (let Ok($ok:ident) = $expr:expr, Err($err:ident) => $($args:tt)+) => {
+    let $ok = match $expr {
+      Ok(value) => value,
+      Err($err) => fail!($($args)+),
+    };
+  };
+
   (let $pattern:pat = $expr:expr) => {
     if let $pattern = $expr {
       fail!("Pattern match failed on line {} of `{}`.", line!(), file!());
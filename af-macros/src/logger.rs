@@ -19,3 +19,15 @@ macro_rules! logger_init {
     );
   };
 }
+
+/// Enters a logging span that adds fields to each message logged from the
+/// current concurrency scope, or the current thread outside of a scope, until
+/// the returned guard is dropped.
+#[macro_export]
+macro_rules! log_span {
+  ($($key:ident = $value:expr),+ $(,)?) => {
+    __af_lib_macro_helpers::__log_enter_span(&[
+      $((stringify!($key), &$value as &dyn std::fmt::Display)),+
+    ])
+  };
+}
@@ -34,7 +34,7 @@ pub fn run(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     },
 
     _ => quote! {
-      #name().await.map_err(|err| err.to_string())
+      #name().await.map_err(|err| format!("{:#}", err))
     },
   };
 